@@ -0,0 +1,101 @@
+use syn::LitStr;
+
+/// A case convention given with `rename_all = "..."`.
+#[derive(Clone, Copy)]
+pub(crate) enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+}
+
+impl RenameRule {
+    const RULES: [(&'static str, Self); 6] = [
+        ("lowercase", Self::Lower),
+        ("UPPERCASE", Self::Upper),
+        ("PascalCase", Self::Pascal),
+        ("camelCase", Self::Camel),
+        ("snake_case", Self::Snake),
+        ("SCREAMING_SNAKE_CASE", Self::ScreamingSnake),
+    ];
+
+    pub(crate) fn from_lit(lit: &LitStr) -> syn::Result<Self> {
+        let value = lit.value();
+
+        match Self::RULES.iter().find(|(name, _)| *name == value) {
+            Some((_, rule)) => Ok(*rule),
+            None => Err(syn::Error::new(
+                lit.span(),
+                concat!(
+                    "the case convention must be one of: ",
+                    "`lowercase`, `UPPERCASE`, `PascalCase`, `camelCase`, `snake_case`, ",
+                    "`SCREAMING_SNAKE_CASE`"
+                ),
+            )),
+        }
+    }
+
+    /// Apply the convention to a `snake_case` field name or a `PascalCase` variant name.
+    pub(crate) fn apply(self, name: &str) -> String {
+        let words = split_words(name);
+
+        match self {
+            Self::Lower => words.concat(),
+            Self::Upper => words.concat().to_uppercase(),
+            Self::Pascal => words.iter().map(|word| capitalize(word)).collect(),
+            Self::Camel => {
+                let mut words = words.iter();
+                let first = words.next().cloned().unwrap_or_default();
+
+                first + &words.map(|word| capitalize(word)).collect::<String>()
+            }
+            Self::Snake => words.join("_"),
+            Self::ScreamingSnake => words.join("_").to_uppercase(),
+        }
+    }
+}
+
+/// Split a name into lowercase words, at underscores and at the start of capitalized words.
+fn split_words(name: &str) -> Vec<String> {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut words = Vec::new();
+    let mut word = String::new();
+
+    for (i, &ch) in chars.iter().enumerate() {
+        if ch == '_' {
+            if !word.is_empty() {
+                words.push(core::mem::take(&mut word));
+            }
+            continue;
+        }
+
+        if ch.is_uppercase() && !word.is_empty() {
+            let prev = chars[i - 1];
+            let next_is_lowercase = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+
+            // `HTTPServer` is split into `HTTP` and `Server`
+            if !prev.is_uppercase() || next_is_lowercase {
+                words.push(core::mem::take(&mut word));
+            }
+        }
+
+        word.extend(ch.to_lowercase());
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
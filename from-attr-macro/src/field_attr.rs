@@ -1,13 +1,41 @@
 use from_attr_core::Array;
-use syn::{meta::ParseNestedMeta, spanned::Spanned, Attribute, Expr, Ident, LitStr, Token};
+use proc_macro2::{TokenStream, TokenTree};
+use syn::{
+    ext::IdentExt,
+    meta::ParseNestedMeta,
+    parenthesized,
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    token, Attribute, Expr, Ident, LitStr, Token,
+};
 
-use crate::{flag_or_value::FlagOrValue, ATTRIBUTE_IDENT};
+use crate::{flag_or_value::FlagOrValue, merge::MergeStrategy, ATTRIBUTE_IDENT};
 
 #[derive(Default)]
 struct FieldAttrOptions {
     rename: Option<String>,
+    alias: Vec<String>,
     default: FlagOrValue<Expr>,
+    default_with: Option<DefaultWith>,
+    skip: bool,
     conflicts: Option<Array<Ident>>,
+    requires: Option<Array<Ident>>,
+    required: bool,
+    required_if: Option<Condition>,
+    forbidden_if: Option<Condition>,
+    positional: bool,
+    multiple: bool,
+    negatable: bool,
+    flag: Option<Expr>,
+    merge: Option<MergeStrategy>,
+    flatten: bool,
+    rest: bool,
+    validate: Option<Expr>,
+    range: Option<TokenStream>,
+    non_empty: bool,
+    max_len: Option<Expr>,
+    unique: bool,
+    pattern: Option<Pattern>,
 }
 
 impl FieldAttrOptions {
@@ -32,6 +60,20 @@ impl FieldAttrOptions {
             return Ok(());
         }
 
+        if meta_path.is_ident("alias") {
+            let input = meta.value()?;
+
+            if input.peek(token::Bracket) {
+                let array = input.parse::<Array<LitStr>>()?;
+                self.alias
+                    .extend(array.elems.into_iter().map(|alias| alias.value()));
+            } else {
+                self.alias.push(input.parse::<LitStr>()?.value());
+            }
+
+            return Ok(());
+        }
+
         if meta_path.is_ident("default") {
             match self.default {
                 FlagOrValue::Flag | FlagOrValue::Value(_) => {
@@ -48,13 +90,172 @@ impl FieldAttrOptions {
             return Ok(());
         }
 
+        if meta_path.is_ident("default_with") {
+            check_duplicate!(default_with);
+            self.default_with = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+
+        if meta_path.is_ident("skip") {
+            if self.skip {
+                return Err(meta.error("duplicate `skip` argument"));
+            }
+
+            self.skip = true;
+            return Ok(());
+        }
+
         if meta_path.is_ident("conflicts") {
             check_duplicate!(conflicts);
             self.conflicts = Some(meta.value()?.parse()?);
             return Ok(());
         }
 
-        Err(meta.error("the argument must be one of: `rename`, `default`, `conflicts`"))
+        if meta_path.is_ident("requires") {
+            check_duplicate!(requires);
+            self.requires = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+
+        if meta_path.is_ident("required") {
+            if self.required {
+                return Err(meta.error("duplicate `required` argument"));
+            }
+
+            self.required = true;
+            return Ok(());
+        }
+
+        if meta_path.is_ident("required_if") {
+            check_duplicate!(required_if);
+            self.required_if = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+
+        if meta_path.is_ident("forbidden_if") {
+            check_duplicate!(forbidden_if);
+            self.forbidden_if = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+
+        if meta_path.is_ident("positional") {
+            if self.positional {
+                return Err(meta.error("duplicate `positional` argument"));
+            }
+
+            self.positional = true;
+            return Ok(());
+        }
+
+        if meta_path.is_ident("multiple") {
+            if self.multiple {
+                return Err(meta.error("duplicate `multiple` argument"));
+            }
+
+            self.multiple = true;
+            return Ok(());
+        }
+
+        if meta_path.is_ident("negatable") {
+            if self.negatable {
+                return Err(meta.error("duplicate `negatable` argument"));
+            }
+
+            self.negatable = true;
+            return Ok(());
+        }
+
+        if meta_path.is_ident("flag") {
+            check_duplicate!(flag);
+            self.flag = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+
+        if meta_path.is_ident("merge") {
+            check_duplicate!(merge);
+            self.merge = Some(MergeStrategy::from_lit(&meta.value()?.parse()?)?);
+            return Ok(());
+        }
+
+        if meta_path.is_ident("flatten") {
+            if self.flatten {
+                return Err(meta.error("duplicate `flatten` argument"));
+            }
+
+            self.flatten = true;
+            return Ok(());
+        }
+
+        if meta_path.is_ident("rest") {
+            if self.rest {
+                return Err(meta.error("duplicate `rest` argument"));
+            }
+
+            self.rest = true;
+            return Ok(());
+        }
+
+        if meta_path.is_ident("validate") {
+            check_duplicate!(validate);
+            self.validate = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+
+        if meta_path.is_ident("range") {
+            check_duplicate!(range);
+
+            let input = meta.value()?;
+            let mut range = TokenStream::new();
+
+            while !input.is_empty() && !input.peek(Token![,]) {
+                range.extend([input.parse::<TokenTree>()?]);
+            }
+
+            if range.is_empty() {
+                return Err(meta.error("expected a range, like `range = 1..=64`"));
+            }
+
+            self.range = Some(range);
+            return Ok(());
+        }
+
+        if meta_path.is_ident("non_empty") {
+            if self.non_empty {
+                return Err(meta.error("duplicate `non_empty` argument"));
+            }
+
+            self.non_empty = true;
+            return Ok(());
+        }
+
+        if meta_path.is_ident("max_len") {
+            check_duplicate!(max_len);
+            self.max_len = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+
+        if meta_path.is_ident("unique") {
+            if self.unique {
+                return Err(meta.error("duplicate `unique` argument"));
+            }
+
+            self.unique = true;
+            return Ok(());
+        }
+
+        if meta_path.is_ident("pattern") {
+            check_duplicate!(pattern);
+            self.pattern = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+
+        Err(meta.error(concat!(
+            "the argument must be one of: ",
+            "`rename`, `alias`, `default`, `default_with`, `skip`, `conflicts`, `requires`, ",
+            "`required`, `required_if`, `forbidden_if`, `positional`, `multiple`, `negatable`, ",
+            "`flag`, `merge`, `flatten`, `rest`, `validate`, `range`, `non_empty`, `max_len`, ",
+            "`unique`, `pattern`"
+        )))
     }
 
     fn parse_attr(&mut self, attr: &Attribute) -> syn::Result<()> {
@@ -65,8 +266,106 @@ impl FieldAttrOptions {
 #[derive(Default)]
 pub(crate) struct FieldAttr {
     pub(crate) rename: Option<String>,
+    pub(crate) alias: Vec<String>,
     pub(crate) default: FlagOrValue<Expr>,
+    pub(crate) default_with: Option<DefaultWith>,
+    pub(crate) skip: bool,
     pub(crate) conflicts: Vec<Ident>,
+    pub(crate) requires: Vec<Ident>,
+    pub(crate) required: bool,
+    pub(crate) required_if: Option<Condition>,
+    pub(crate) forbidden_if: Option<Condition>,
+    pub(crate) positional: bool,
+    pub(crate) multiple: bool,
+    pub(crate) negatable: bool,
+    pub(crate) flag: Option<Expr>,
+    pub(crate) merge: Option<MergeStrategy>,
+    pub(crate) flatten: bool,
+    pub(crate) rest: bool,
+    pub(crate) validate: Option<Expr>,
+    pub(crate) range: Option<TokenStream>,
+    pub(crate) non_empty: bool,
+    pub(crate) max_len: Option<Expr>,
+    pub(crate) unique: bool,
+    pub(crate) pattern: Option<Pattern>,
+}
+
+/// A function computing the default value of a field, like `path::to::fn` or
+/// `(path::to::fn, [a, b])`, called with references to the listed fields.
+pub(crate) struct DefaultWith {
+    pub(crate) function: Expr,
+    pub(crate) inputs: Vec<Ident>,
+}
+
+impl Parse for DefaultWith {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if !input.peek(token::Paren) {
+            return Ok(Self {
+                function: input.parse()?,
+                inputs: Vec::new(),
+            });
+        }
+
+        let content;
+        parenthesized!(content in input);
+
+        let function = content.parse()?;
+        content.parse::<Token![,]>()?;
+        let inputs = content.parse::<Array<Ident>>()?;
+
+        if !content.is_empty() {
+            return Err(content.error("expected `(path::to::fn, [a, b])`"));
+        }
+
+        Ok(Self {
+            function,
+            inputs: inputs.elems.into_iter().collect(),
+        })
+    }
+}
+
+/// A condition on the value of another field, like `(mode = Mode::Bounded)`, the value is an
+/// expression compared with the converted value of the field.
+pub(crate) struct Condition {
+    pub(crate) ident: Ident,
+    pub(crate) value: Expr,
+}
+
+impl Parse for Condition {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        let ident = content.parse()?;
+        content.parse::<Token![=]>()?;
+        let value = content.parse()?;
+
+        Ok(Self { ident, value })
+    }
+}
+
+/// The string patterns that can be checked with `pattern = ...`.
+#[derive(Clone, Copy)]
+pub(crate) enum Pattern {
+    Ident,
+    Path,
+    Type,
+}
+
+impl Parse for Pattern {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident = input.call(Ident::parse_any)?;
+
+        match ident.to_string().as_str() {
+            "ident" => Ok(Self::Ident),
+            "path" => Ok(Self::Path),
+            "type" => Ok(Self::Type),
+            _ => Err(syn::Error::new(
+                ident.span(),
+                "expected one of: `ident`, `path`, `type`",
+            )),
+        }
+    }
 }
 
 impl FieldAttr {
@@ -103,16 +402,58 @@ impl FieldAttr {
 
         let FieldAttrOptions {
             rename,
+            alias,
             default,
+            default_with,
+            skip,
             conflicts,
+            requires,
+            required,
+            required_if,
+            forbidden_if,
+            positional,
+            multiple,
+            negatable,
+            flag,
+            merge,
+            flatten,
+            rest,
+            validate,
+            range,
+            non_empty,
+            max_len,
+            unique,
+            pattern,
         } = options;
 
         Ok(Some(Self {
             rename,
+            alias,
             default,
+            default_with,
+            skip,
             conflicts: conflicts
                 .map(|conflicts| conflicts.elems.into_iter().collect())
                 .unwrap_or_default(),
+            requires: requires
+                .map(|requires| requires.elems.into_iter().collect())
+                .unwrap_or_default(),
+            required,
+            required_if,
+            forbidden_if,
+            positional,
+            multiple,
+            negatable,
+            flag,
+            merge,
+            flatten,
+            rest,
+            validate,
+            range,
+            non_empty,
+            max_len,
+            unique,
+            pattern,
         }))
     }
 }
@@ -1,14 +1,23 @@
 use std::collections::{HashMap, HashSet};
 
 use proc_macro2::TokenStream;
-use quote::format_ident;
-use quote_use::quote_use;
+use quote::{format_ident, quote_spanned};
+use quote_use::{quote_spanned_use, quote_use};
 use syn::{
-    parse_quote, punctuated::Punctuated, Data, DataEnum, DataStruct, DataUnion, DeriveInput, Field,
-    Fields, FieldsNamed, Ident, ImplGenerics, Token, TypeGenerics, Visibility, WhereClause,
+    ext::IdentExt, parse_quote, punctuated::Punctuated, spanned::Spanned, Data, DataEnum,
+    DataStruct, DataUnion, DeriveInput, Expr, Field, Fields, FieldsNamed, FieldsUnnamed, Ident,
+    ImplGenerics, Index, LitStr, Member, Token, Type, TypeGenerics, TypePath, Variant, Visibility,
+    WhereClause,
 };
 
-use crate::{field_attr::FieldAttr, flag_or_value::FlagOrValue, struct_attr::StructAttr};
+use crate::{
+    case::RenameRule,
+    field_attr::{Condition, DefaultWith, FieldAttr, Pattern},
+    flag_or_value::FlagOrValue,
+    merge::MergeStrategy,
+    struct_attr::{ArgGroup, ArgGroupKind, StructAttr},
+    variant_attr::VariantAttr,
+};
 
 pub(crate) fn generate(input: DeriveInput) -> syn::Result<TokenStream> {
     let DeriveInput {
@@ -21,6 +30,15 @@ pub(crate) fn generate(input: DeriveInput) -> syn::Result<TokenStream> {
 
     let Some(StructAttr {
         idents: attr_idents,
+        tag,
+        rename_all,
+        nested,
+        allow_unknown,
+        merge,
+        once,
+        default,
+        validate,
+        groups,
     }) = StructAttr::parse_attrs(&input_attrs)?
     else {
         return Err(syn::Error::new(
@@ -33,7 +51,86 @@ pub(crate) fn generate(input: DeriveInput) -> syn::Result<TokenStream> {
 
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    match data {
+    if let (Some(tag), Data::Struct(_) | Data::Union(_)) = (&tag, &data) {
+        return Err(syn::Error::new(
+            tag.span(),
+            "`tag` can only be used on enums",
+        ));
+    }
+
+    if let Some(group) = groups.first() {
+        if !matches!(&data, Data::Struct(DataStruct { fields, .. }) if !fields.is_empty()) {
+            return Err(syn::Error::new(
+                group.span,
+                "`one_of` and `any_of` can only be used on structs with fields",
+            ));
+        }
+    }
+
+    if allow_unknown
+        && !matches!(&data, Data::Struct(DataStruct { fields, .. }) if !fields.is_empty())
+    {
+        return Err(syn::Error::new(
+            input_ident.span(),
+            "`allow_unknown` can only be used on structs with fields",
+        ));
+    }
+
+    if merge != MergeStrategy::Error
+        && !matches!(&data, Data::Struct(DataStruct { fields, .. }) if !fields.is_empty())
+    {
+        return Err(syn::Error::new(
+            input_ident.span(),
+            "`merge` can only be used on structs with fields",
+        ));
+    }
+
+    if default && !matches!(&data, Data::Struct(_)) {
+        return Err(syn::Error::new(
+            input_ident.span(),
+            "`default` can only be used on structs",
+        ));
+    }
+
+    let attribute_ident_impl = {
+        let once = once.then(|| {
+            quote_use!(
+                const ONCE: bool = true;
+            )
+        });
+
+        quote_use! {
+            # use from_attr::AttributeIdent;
+
+            #[automatically_derived]
+            impl #impl_generics AttributeIdent for #input_ident #ty_generics #where_clause {
+                const IDENTS: &'static [&'static str] = &[#(#attr_idents),*];
+                #once
+            }
+        }
+    };
+
+    let nested_impl = nested.then(|| {
+        quote_use! {
+            # use from_attr::__internal::syn::meta::ParseNestedMeta;
+            # use from_attr::{ConvertParsed, Error, Nested, PathValue};
+
+            #[automatically_derived]
+            impl #impl_generics ConvertParsed for #input_ident #ty_generics #where_clause {
+                type Type = Nested<Self>;
+
+                fn convert(path_value: PathValue<Self::Type>) -> Result<Self, Error> {
+                    path_value.value.into_value()
+                }
+
+                fn list(meta: &ParseNestedMeta) -> Option<Result<Self::Type, Error>> {
+                    Some(Nested::parse_list(meta))
+                }
+            }
+        }
+    });
+
+    let expand = match data {
         Data::Struct(DataStruct { fields, .. }) => match fields {
             Fields::Named(FieldsNamed { named, .. }) => {
                 if named.is_empty() {
@@ -45,18 +142,23 @@ pub(crate) fn generate(input: DeriveInput) -> syn::Result<TokenStream> {
                         impl_generics,
                         ty_generics,
                         where_clause,
-                        attr_idents,
+                        validate.as_ref(),
                     )
                 } else {
-                    generate_named_fields(
+                    generate_fields(
                         named,
+                        &groups,
+                        rename_all,
+                        allow_unknown,
+                        merge,
+                        default,
                         vis,
                         parser_struct_ident,
                         input_ident,
                         impl_generics,
                         ty_generics,
                         where_clause,
-                        attr_idents,
+                        validate.as_ref(),
                     )
                 }
             }
@@ -68,22 +170,88 @@ pub(crate) fn generate(input: DeriveInput) -> syn::Result<TokenStream> {
                 impl_generics,
                 ty_generics,
                 where_clause,
-                attr_idents,
+                validate.as_ref(),
             ),
-            Fields::Unnamed(_) => Err(syn::Error::new_spanned(
-                fields,
-                "expected a struct with named fields or unit struct",
-            )),
+            Fields::Unnamed(FieldsUnnamed { unnamed, .. }) => {
+                if unnamed.is_empty() {
+                    generate_unit(
+                        true,
+                        vis,
+                        parser_struct_ident,
+                        input_ident,
+                        impl_generics,
+                        ty_generics,
+                        where_clause,
+                        validate.as_ref(),
+                    )
+                } else {
+                    generate_fields(
+                        unnamed,
+                        &groups,
+                        rename_all,
+                        allow_unknown,
+                        merge,
+                        default,
+                        vis,
+                        parser_struct_ident,
+                        input_ident,
+                        impl_generics,
+                        ty_generics,
+                        where_clause,
+                        validate.as_ref(),
+                    )
+                }
+            }
         },
-        Data::Enum(DataEnum { enum_token, .. }) => Err(syn::Error::new(
-            enum_token.span,
-            "expected a struct with named fields or unit struct",
-        )),
+        Data::Enum(DataEnum {
+            brace_token,
+            variants,
+            ..
+        }) => {
+            if variants.is_empty() {
+                return Err(syn::Error::new(
+                    brace_token.span.join(),
+                    "`FromAttr` cannot be derived for empty enums",
+                ));
+            }
+
+            match tag {
+                Some(tag) => generate_tagged_enum(
+                    tag,
+                    variants,
+                    rename_all,
+                    vis,
+                    parser_struct_ident,
+                    input_ident,
+                    impl_generics,
+                    ty_generics,
+                    where_clause,
+                    validate.as_ref(),
+                ),
+                None => generate_enum(
+                    variants,
+                    rename_all,
+                    vis,
+                    parser_struct_ident,
+                    input_ident,
+                    impl_generics,
+                    ty_generics,
+                    where_clause,
+                    validate.as_ref(),
+                ),
+            }
+        }
         Data::Union(DataUnion { union_token, .. }) => Err(syn::Error::new(
             union_token.span,
-            "expected a struct with named fields or unit struct",
+            "expected a struct or enum",
         )),
-    }
+    }?;
+
+    Ok(quote_use! {
+        #expand
+        #attribute_ident_impl
+        #nested_impl
+    })
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -95,7 +263,7 @@ fn generate_unit<'a>(
     impl_generics: ImplGenerics<'a>,
     ty_generics: TypeGenerics<'a>,
     where_clause: Option<&'a WhereClause>,
-    attr_idents: Vec<String>,
+    validate: Option<&Expr>,
 ) -> syn::Result<TokenStream> {
     let construct_self = if has_brace_token {
         quote_use! {
@@ -107,11 +275,19 @@ fn generate_unit<'a>(
         }
     };
 
+    let construct_self = construct(None, construct_self, validate, quote_use!(spans));
+
+    let spans_ident = if validate.is_some() {
+        quote_use!(spans)
+    } else {
+        quote_use!(_)
+    };
+
     let expand = quote_use! {
         # use core::default::Default;
-        # use from_attr::__internal::syn::{self, Token, meta::ParseNestedMeta};
+        # use from_attr::__internal::syn::{Token, meta::ParseNestedMeta};
         # use from_attr::__internal::proc_macro2::Span;
-        # use from_attr::{AttributeIdent, FromAttr, ParseMeta};
+        # use from_attr::{AttributeIdent, Error, FromAttr, ParseMeta};
 
         #[doc(hidden)]
         #[allow(non_camel_case_types)]
@@ -124,22 +300,64 @@ fn generate_unit<'a>(
                 true
             }
 
-            fn parse_meta(&mut self, _: ParseNestedMeta) -> syn::Result<()> {
+            fn parse_meta(&mut self, _: ParseNestedMeta) -> Result<(), Error> {
                 Ok(())
             }
         }
 
         #[automatically_derived]
-        impl #impl_generics AttributeIdent for #struct_ident #ty_generics #where_clause {
-            const IDENTS: &'static [&'static str] = &[#(#attr_idents),*];
+        impl #impl_generics FromAttr for #struct_ident #ty_generics #where_clause {
+            type Parser = #parser_struct_ident;
+
+            fn from_parser(parser: Self::Parser, #spans_ident: &[Span]) -> Result<Self, Error> {
+                #construct_self
+            }
         }
+    };
+
+    Ok(expand)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn generate_fields<'a>(
+    fields: Punctuated<Field, Token![,]>,
+    groups: &[ArgGroup],
+    rename_all: Option<RenameRule>,
+    allow_unknown: bool,
+    merge: MergeStrategy,
+    default: bool,
+    vis: Visibility,
+    parser_struct_ident: Ident,
+    struct_ident: Ident,
+    impl_generics: ImplGenerics<'a>,
+    ty_generics: TypeGenerics<'a>,
+    where_clause: Option<&'a WhereClause>,
+    validate: Option<&Expr>,
+) -> syn::Result<TokenStream> {
+    let generated_fields = GeneratedFields::parse(fields, groups, rename_all, merge, default)?;
+    let parser_struct = generated_fields.parser_struct(&vis, &parser_struct_ident, allow_unknown);
+
+    let members = &generated_fields.members;
+
+    let construct_self = construct(
+        Some(&generated_fields),
+        quote_use!(Self {#members}),
+        validate,
+        quote_use!(spans),
+    );
+
+    let expand = quote_use! {
+        # use from_attr::__internal::proc_macro2::Span;
+        # use from_attr::{AttributeIdent, Error, FromAttr};
+
+        #parser_struct
 
         #[automatically_derived]
         impl #impl_generics FromAttr for #struct_ident #ty_generics #where_clause {
             type Parser = #parser_struct_ident;
 
-            fn from_parser(parser: Self::Parser, _: &[Span]) -> syn::Result<Self> {
-                Ok(#construct_self)
+            fn from_parser(parser: Self::Parser, spans: &[Span]) -> Result<Self, Error> {
+                #construct_self
             }
         }
     };
@@ -148,121 +366,322 @@ fn generate_unit<'a>(
 }
 
 #[allow(clippy::too_many_arguments)]
-fn generate_named_fields<'a>(
-    named: Punctuated<Field, Token![,]>,
+fn generate_enum<'a>(
+    variants: Punctuated<Variant, Token![,]>,
+    rename_all: Option<RenameRule>,
     vis: Visibility,
     parser_struct_ident: Ident,
-    struct_ident: Ident,
+    enum_ident: Ident,
     impl_generics: ImplGenerics<'a>,
     ty_generics: TypeGenerics<'a>,
     where_clause: Option<&'a WhereClause>,
-    attr_idents: Vec<String>,
+    validate: Option<&Expr>,
 ) -> syn::Result<TokenStream> {
-    let mut conflicts: HashSet<(Ident, Ident)> = HashSet::new();
+    let mut errors = Vec::new();
+    let mut variant_parsers: Vec<TokenStream> = Vec::new();
     let mut fields: Punctuated<TokenStream, Token![,]> = Punctuated::new();
     let mut parse_arguments: Vec<TokenStream> = Vec::new();
-    let mut field_values: Punctuated<TokenStream, Token![,]> = Punctuated::new();
-    let mut defined_fields: HashMap<Ident, String> = HashMap::new();
+    let mut given_variants: Vec<TokenStream> = Vec::new();
+    let mut construct_variants: Vec<TokenStream> = Vec::new();
+    let mut variant_keys: Vec<String> = Vec::new();
 
-    for field in named.into_iter() {
-        handle_single_field(
-            field,
-            &mut conflicts,
-            &mut fields,
-            &mut parse_arguments,
-            &mut field_values,
-            &mut defined_fields,
-        )?;
-    }
+    for Variant {
+        attrs,
+        ident: variant_ident,
+        fields: variant_fields,
+        ..
+    } in variants
+    {
+        let VariantAttr { rename } = match VariantAttr::parse_attrs(&attrs) {
+            Ok(o) => o.unwrap_or_default(),
+            Err(e) => {
+                errors.push(e);
+                continue;
+            }
+        };
 
-    let conflicts_check = conflicts
-        .into_iter()
-        .flat_map(|(a, b)| -> Option<_> {
-            let a_rename = defined_fields.get(&a)?;
-            let b_rename = defined_fields.get(&b)?;
-            let a_to_b_err_msg =
-                format!("`{a_rename}` conflicts with mutually exclusive `{b_rename}`");
-            let b_to_a_err_msg =
-                format!("`{b_rename}` conflicts with mutually exclusive `{a_rename}`");
+        let variant_key = rename.unwrap_or_else(|| {
+            rename_all
+                .unwrap_or(RenameRule::Snake)
+                .apply(&variant_ident.to_string())
+        });
 
-            Some(quote_use! {
-                # use from_attr::__internal::syn;
+        let other_variants_check = quote_use! {
+            # use core::option::Option::Some;
+            # use core::result::Result::Err;
+            # use std::format;
+            # use std::string::ToString;
+            # use from_attr::{Diagnostic, Error, ErrorKind};
+
+            if let Some((given_key, given_span)) = self.given_variant() {
+                return Err(Error::new(
+                    ErrorKind::Conflict {
+                        name: ToString::to_string(#variant_key),
+                        other: ToString::to_string(given_key),
+                        other_span: given_span,
+                    },
+                    Diagnostic::new(
+                        meta_path_span,
+                        format!("`{}` conflicts with mutually exclusive `{}`", #variant_key, given_key),
+                    )
+                    .note(given_span, format!("`{}` is given here", given_key)),
+                ));
+            }
+        };
+
+        let duplicate_check = quote_use! {
+            # use core::option::Option::Some;
+            # use core::result::Result::Err;
+            # use std::string::ToString;
+            # use from_attr::{Diagnostic, Error, ErrorKind};
+
+            if let Some((_, first)) = self.given_variant().filter(|_| self.#variant_ident.is_some()) {
+                return Err(Error::new(
+                    ErrorKind::DuplicateField { name: ToString::to_string(#variant_key), first },
+                    Diagnostic::new(meta_path_span, concat!("duplicate `", #variant_key, "` argument"))
+                        .note(first, "first defined here"),
+                ));
+            }
+        };
+
+        match variant_fields {
+            Fields::Unit => {
+                let unexpected_value_msg = format!("`{variant_key}` does not take a value");
+
+                fields.push(quote_use! {
+                    # use core::option::Option;
+                    # use from_attr::__internal::proc_macro2::Span;
+
+                    #variant_ident: Option<Span>
+                });
+
+                parse_arguments.push(quote_use! {
+                    # use core::option::Option::Some;
+                    # use core::result::Result::{Ok, Err};
+                    # use from_attr::__internal::syn::{token, Token};
+
+                    if meta_path.is_ident(#variant_key) {
+                        #duplicate_check
+                        #other_variants_check
 
-                if let (Some(a), Some(b)) = (&parser.#a, &parser.#b) {
-                    if let Some(joined_span) = a.path.join(b.path) {
-                        return Err(syn::Error::new(joined_span, #a_to_b_err_msg));
-                    } else {
-                        let mut err = syn::Error::new(a.path, #a_to_b_err_msg);
-                        err.combine(syn::Error::new(b.path, #b_to_a_err_msg));
-                        return Err(err);
+                        if meta.input.peek(Token![=]) || meta.input.peek(token::Paren) {
+                            return Err(meta.error(#unexpected_value_msg).into());
+                        }
+
+                        self.#variant_ident = Some(meta_path_span);
+                        return Ok(());
                     }
-                }
+                });
+
+                given_variants.push(quote_use! {
+                    # use core::option::Option::Some;
+
+                    if let Some(span) = self.#variant_ident {
+                        return Some((#variant_key, span));
+                    }
+                });
+
+                let construct_variant = construct(
+                    None,
+                    quote_use!(Self::#variant_ident),
+                    validate,
+                    quote_use!(spans),
+                );
+
+                construct_variants.push(quote_use! {
+                    if parser.#variant_ident.is_some() {
+                        return #construct_variant;
+                    }
+                });
+            }
+            Fields::Named(FieldsNamed {
+                named: fields_to_parse,
+                ..
             })
-        })
-        .collect::<Vec<_>>();
-
-    let unknown_field_msg = match defined_fields.len() {
-        1 => format!(
-            "expected field: `{}`",
-            defined_fields
-                .values()
-                .next()
-                .expect("expected at least one field")
-        ),
-        _ => {
-            let fields = defined_fields
-                .values()
-                .map(|a| format!("`{}`", a))
-                .collect::<Vec<_>>()
-                .join(", ");
+            | Fields::Unnamed(FieldsUnnamed {
+                unnamed: fields_to_parse,
+                ..
+            }) => {
+                let generated_fields = match GeneratedFields::parse(
+                    fields_to_parse,
+                    &[],
+                    None,
+                    MergeStrategy::Error,
+                    false,
+                ) {
+                    Ok(o) => o,
+                    Err(e) => {
+                        errors.push(e);
+                        continue;
+                    }
+                };
+
+                if generated_fields.merges {
+                    errors.push(syn::Error::new(
+                        variant_ident.span(),
+                        "`merge` cannot be used in variants of enums",
+                    ));
+                    continue;
+                }
+
+                let variant_parser_ident = format_ident!("{parser_struct_ident}_{variant_ident}");
+                let expected_list_msg = format!("expected `{variant_key}(...)`");
+
+                variant_parsers.push(generated_fields.parser_struct(
+                    &vis,
+                    &variant_parser_ident,
+                    false,
+                ));
+
+                let members = &generated_fields.members;
+
+                let construct_variant = construct(
+                    Some(&generated_fields),
+                    quote_use!(Self::#variant_ident {#members}),
+                    validate,
+                    quote_use!(attr_spans),
+                );
+
+                // the validator of the enum receives the spans of the attributes
+                let attr_spans = validate.map(|_| {
+                    quote_use! {
+                        let attr_spans = spans;
+                    }
+                });
+
+                fields.push(quote_use! {
+                    # use core::option::Option;
+                    # use from_attr::PathValue;
+
+                    #variant_ident: Option<PathValue<#variant_parser_ident>>
+                });
+
+                parse_arguments.push(quote_use! {
+                    # use core::default::Default;
+                    # use core::option::Option::Some;
+                    # use core::result::Result::{Ok, Err};
+                    # use from_attr::{ParseMeta, PathValue};
+                    # use from_attr::__internal::syn::{token, Token};
+
+                    if meta_path.is_ident(#variant_key) {
+                        #duplicate_check
+                        #other_variants_check
+
+                        if meta.input.peek(Token![=]) {
+                            return Err(meta.error(#expected_list_msg).into());
+                        }
+
+                        // #[one(a(b, c))]
+                        // if `b`, `c` all have default value,
+                        // it should be allowed to be written as `#[one(a)]`
+                        let mut variant_parser = <#variant_parser_ident as Default>::default();
+
+                        if meta.input.peek(token::Paren) {
+                            variant_parser.parse_nested(&meta)?;
+                        }
+
+                        self.#variant_ident = Some(PathValue { path: meta_path_span, value: variant_parser });
+                        return Ok(());
+                    }
+                });
 
-            format!("expected fields: {}", fields)
+                given_variants.push(quote_use! {
+                    # use core::option::Option::Some;
+
+                    if let Some(variant) = &self.#variant_ident {
+                        return Some((#variant_key, variant.path));
+                    }
+                });
+
+                construct_variants.push(quote_use! {
+                    # use core::option::Option::Some;
+                    # use from_attr::PathValue;
+                    # use from_attr::__internal::proc_macro2::Span;
+
+                    if let Some(PathValue { path, value: parser }) = parser.#variant_ident {
+                        // missing fields of the variant are reported on the variant key
+                        #attr_spans
+                        let spans: &[Span] = &[path];
+                        return #construct_variant;
+                    }
+                });
+            }
         }
-    };
+
+        variant_keys.push(variant_key);
+    }
+
+    if let Some(e) = errors.into_iter().reduce(|mut a, b| {
+        a.combine(b);
+        a
+    }) {
+        return Err(e);
+    }
+
+    let expected_variants = variant_keys
+        .iter()
+        .map(|a| format!("`{}`", a))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let missing_variant_msg = format!("missing one of: {}", expected_variants);
 
     let expand = quote_use! {
         # use core::default::Default;
-        # use from_attr::__internal::syn::{self, Token, meta::ParseNestedMeta, spanned::Spanned};
+        # use core::iter::Iterator;
+        # use core::option::Option::{self, None};
+        # use std::string::ToString;
+        # use from_attr::__internal::syn::{Token, meta::ParseNestedMeta, spanned::Spanned};
         # use from_attr::__internal::proc_macro2::Span;
-        # use from_attr::{AttributeIdent, FromAttr, ParseMeta};
+        # use from_attr::__internal::{unknown_argument_msg, unknown_field_error};
+        # use from_attr::{AttributeIdent, Diagnostics, Error, ErrorKind, FromAttr, ParseMeta};
+
+        #(#variant_parsers)*
 
         #[doc(hidden)]
-        #[allow(non_camel_case_types)]
+        #[allow(non_camel_case_types, non_snake_case)]
         #[derive(Default)]
         #vis struct #parser_struct_ident {
             #fields
         }
 
+        impl #parser_struct_ident {
+            fn given_variant(&self) -> Option<(&'static str, Span)> {
+                #(#given_variants)*
+                None
+            }
+        }
+
         #[automatically_derived]
         impl ParseMeta for #parser_struct_ident {
+            const FIELDS: &'static [&'static str] = &[#(#variant_keys),*];
+
             fn is_unit() -> bool {
                 false
             }
 
-            fn parse_meta(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+            fn parse_meta(&mut self, meta: ParseNestedMeta) -> Result<(), Error> {
                 let meta_path = &meta.path;
                 let meta_path_span = Spanned::span(meta_path);
 
-                let next_token_is_not_eq = !meta.input.peek(Token![=]);
-
                 #(#parse_arguments)*
 
-                Err(meta.error(#unknown_field_msg))
+                Err(unknown_field_error(&meta, unknown_argument_msg(meta_path, Self::FIELDS, "expected one of")))
             }
         }
 
         #[automatically_derived]
-        impl #impl_generics AttributeIdent for #struct_ident #ty_generics #where_clause {
-            const IDENTS: &'static [&'static str] = &[#(#attr_idents),*];
-        }
-
-        #[automatically_derived]
-        impl #impl_generics FromAttr for #struct_ident #ty_generics #where_clause {
+        impl #impl_generics FromAttr for #enum_ident #ty_generics #where_clause {
             type Parser = #parser_struct_ident;
 
-            fn from_parser(parser: Self::Parser, spans: &[Span]) -> syn::Result<Self> {
-                #(#conflicts_check)*
-                Ok(Self {#field_values})
+            fn from_parser(parser: Self::Parser, spans: &[Span]) -> Result<Self, Error> {
+                #(#construct_variants)*
+
+                let names = <#parser_struct_ident as ParseMeta>::FIELDS.iter().map(ToString::to_string).collect();
+
+                Err(Diagnostics::spanned(spans, ErrorKind::Group { names }, #missing_variant_msg)
+                    .into_error()
+                    .expect("`spans` is not empty"))
             }
         }
     };
@@ -270,127 +689,2301 @@ fn generate_named_fields<'a>(
     Ok(expand)
 }
 
-fn handle_single_field<'a>(
-    field: Field,
-    conflicts: &'a mut HashSet<(Ident, Ident)>,
-    fields: &'a mut Punctuated<TokenStream, Token![,]>,
-    parse_arguments: &'a mut Vec<TokenStream>,
-    field_values: &'a mut Punctuated<TokenStream, Token![,]>,
-    defined_fields: &'a mut HashMap<Ident, String>,
-) -> syn::Result<()> {
-    let Field {
-        attrs, ident, ty, ..
-    } = field;
+#[allow(clippy::too_many_arguments)]
+fn generate_tagged_enum<'a>(
+    tag: LitStr,
+    variants: Punctuated<Variant, Token![,]>,
+    rename_all: Option<RenameRule>,
+    vis: Visibility,
+    parser_struct_ident: Ident,
+    enum_ident: Ident,
+    impl_generics: ImplGenerics<'a>,
+    ty_generics: TypeGenerics<'a>,
+    where_clause: Option<&'a WhereClause>,
+    validate: Option<&Expr>,
+) -> syn::Result<TokenStream> {
+    let tag_span = tag.span();
+    let tag = tag.value();
 
-    let FieldAttr {
-        rename,
-        default,
-        conflicts: current_conflicts,
-    } = FieldAttr::parse_attrs(&attrs)?.unwrap_or_default();
+    let mut errors = Vec::new();
+    let mut variant_parsers: Vec<TokenStream> = Vec::new();
+    let mut fields: Punctuated<TokenStream, Token![,]> = Punctuated::new();
+    let mut construct_variants: Vec<TokenStream> = Vec::new();
+    let mut variant_keys: Vec<String> = Vec::new();
+    // every key of the variants, with the variants that accept it
+    let mut keys: Vec<(String, Vec<Ident>)> = Vec::new();
+    // the keys that are the name of a field in at least one variant, the others are aliases
+    let mut field_names: HashSet<String> = HashSet::new();
 
-    let field_ident = ident.expect("expected a named field");
-    let rename_field_ident = rename.unwrap_or_else(|| field_ident.to_string());
+    for Variant {
+        attrs,
+        ident: variant_ident,
+        fields: variant_fields,
+        ..
+    } in variants
+    {
+        let VariantAttr { rename } = match VariantAttr::parse_attrs(&attrs) {
+            Ok(o) => o.unwrap_or_default(),
+            Err(e) => {
+                errors.push(e);
+                continue;
+            }
+        };
 
-    for conflict_field_ident in current_conflicts {
-        if conflict_field_ident < field_ident {
-            conflicts.insert((conflict_field_ident, field_ident.clone()));
-        } else {
-            conflicts.insert((field_ident.clone(), conflict_field_ident));
-        }
-    }
+        let variant_key = rename.unwrap_or_else(|| {
+            rename_all
+                .unwrap_or(RenameRule::Snake)
+                .apply(&variant_ident.to_string())
+        });
+        let not_valid_msg = format!("is not valid for {tag} = {variant_key}");
 
-    fields.push(quote_use! {
-        # use core::option::Option;
-        # use from_attr::{ConvertParsed, PathValue};
+        let (names, variant_aliases, construct_variant) = match variant_fields {
+            Fields::Unit => {
+                let construct_variant = construct(
+                    None,
+                    quote_use!(Self::#variant_ident),
+                    validate,
+                    quote_use!(spans),
+                );
 
-        #field_ident: Option<PathValue<<#ty as ConvertParsed>::Type>>
-    });
+                (
+                    Vec::new(),
+                    Vec::new(),
+                    quote_use! {
+                        check_errors(errors)?;
+                        #construct_variant
+                    },
+                )
+            }
+            Fields::Named(FieldsNamed { named, .. }) => {
+                let generated_fields =
+                    match GeneratedFields::parse(named, &[], None, MergeStrategy::Error, false) {
+                        Ok(o) => o,
+                        Err(e) => {
+                            errors.push(e);
+                            continue;
+                        }
+                    };
 
-    parse_arguments.push(quote_use! {
-        # use core::option::Option::Some;
-        # use std::string::ToString;
-        # use std::format;
-        # use from_attr::{ConvertParsed, PathValue};
-        # use from_attr::merge;
-        # use from_attr::__internal::syn::{self, Token};
+                if generated_fields.merges {
+                    errors.push(syn::Error::new(
+                        variant_ident.span(),
+                        "`merge` cannot be used in variants of enums",
+                    ));
+                    continue;
+                }
 
-        if meta_path.is_ident(#rename_field_ident) {
-            if self.#field_ident.is_some() {
-                return Err(meta.error(concat!("duplicate `", #rename_field_ident, "` argument")));
-            }
+                if !generated_fields.positional_arms.is_empty() {
+                    errors.push(syn::Error::new(
+                        variant_ident.span(),
+                        "positional arguments cannot be used in variants of tagged enums",
+                    ));
+                    continue;
+                }
 
-            let value = if let Some(Some(value)) = next_token_is_not_eq.then(|| <#ty as ConvertParsed>::flag()) {
-                value
-            } else {
-                meta.value()?.parse()?
-            };
+                if !generated_fields.flattened.is_empty() {
+                    errors.push(syn::Error::new(
+                        variant_ident.span(),
+                        "`flatten` cannot be used in variants of tagged enums",
+                    ));
+                    continue;
+                }
 
-            self.#field_ident = Some(PathValue { path: meta_path_span, value });
-            return Ok(());
-        }
-    });
+                if generated_fields.rest.is_some() {
+                    errors.push(syn::Error::new(
+                        variant_ident.span(),
+                        "`rest` cannot be used in variants of tagged enums",
+                    ));
+                    continue;
+                }
 
-    let missing_field_msg = format!(
-        "missing `{i}` field, try `{i} = ...`",
-        i = rename_field_ident
-    );
+                if generated_fields.keys.contains_key(&tag) {
+                    errors.push(syn::Error::new(
+                        variant_ident.span(),
+                        format!("the field `{tag}` conflicts with the tag of the enum"),
+                    ));
+                    continue;
+                }
 
-    let missing_flag_msg = format!(
-        "missing `{i}` flag, try `{i}` or `{i} = ...`",
-        i = rename_field_ident
-    );
+                let variant_parser_ident = format_ident!("{parser_struct_ident}_{variant_ident}");
 
-    let default = match default {
-        FlagOrValue::None => None,
-        FlagOrValue::Flag => Some(parse_quote!(::core::default::Default::default())),
-        FlagOrValue::Value(expr) => Some(expr),
-    };
+                variant_parsers.push(generated_fields.parser_struct(
+                    &vis,
+                    &variant_parser_ident,
+                    false,
+                ));
 
-    field_values.push(match default {
-        None => quote_use! {
-            # use core::option::Option::{Some, None};
-            # use core::result::Result::{Ok, Err};
-            # use from_attr::ConvertParsed;
+                fields.push(quote_use! {
+                    # use std::vec::Vec;
+                    # use from_attr::Error;
+
+                    #variant_ident: (#variant_parser_ident, Vec<Error>)
+                });
+
+                let members = &generated_fields.members;
+
+                let construct_variant = construct(
+                    Some(&generated_fields),
+                    quote_use!(Self::#variant_ident {#members}),
+                    validate,
+                    quote_use!(spans),
+                );
+
+                let construct_variant = quote_use! {
+                    let (parser, variant_errors) = parser.#variant_ident;
+                    check_errors(variant_errors.into_iter().chain(errors))?;
+
+                    #construct_variant
+                };
+
+                (
+                    generated_fields.names,
+                    generated_fields.aliases,
+                    construct_variant,
+                )
+            }
+            Fields::Unnamed(_) => {
+                errors.push(syn::Error::new_spanned(
+                    variant_fields,
+                    "expected a variant with named fields or unit variant",
+                ));
+                continue;
+            }
+        };
+
+        for name in names.iter().chain(&variant_aliases) {
+            match keys.iter_mut().find(|(key, _)| key == name) {
+                Some((_, variant_idents)) => variant_idents.push(variant_ident.clone()),
+                None => keys.push((name.clone(), vec![variant_ident.clone()])),
+            }
+        }
+
+        field_names.extend(names.iter().cloned());
+
+        construct_variants.push(quote_use! {
+            # use std::format;
+            # use std::string::ToString;
+            # use std::vec::Vec;
+            # use from_attr::{Error, ErrorKind};
             # use from_attr::__internal::syn;
 
-            #field_ident: match parser.#field_ident.map(ConvertParsed::convert) {
-                Some(Ok(#field_ident)) => #field_ident,
-                Some(Err(err)) => return Err(err),
-                None => {
-                    if let Some(#field_ident) = <#ty as ConvertParsed>::default() {
-                        #field_ident
-                    } else {
-                        let err = spans
-                            .into_iter()
-                            .map(|span| {
-                                if <#ty as ConvertParsed>::flag().is_some() {
-                                    syn::Error::new(*span, #missing_flag_msg)
-                                } else {
-                                    syn::Error::new(*span, #missing_field_msg)
-                                }
-                            })
-                            .reduce(|mut a, b| {
-                                a.combine(b);
-                                a
-                            })
-                            .expect("`spans` is not empty");
-
-                        return Err(err);
-                    }
-                }
+            #variant_key => {
+                let names: &[&str] = &[#(#names,)* #(#variant_aliases),*];
+                let errors: Vec<Error> = parser
+                    .keys
+                    .iter()
+                    .filter(|(key, _)| !names.contains(key))
+                    .map(|(key, span)| Error::new(
+                        ErrorKind::UnknownField { name: ToString::to_string(key) },
+                        syn::Error::new(*span, format!("`{}` {}", key, #not_valid_msg)),
+                    ))
+                    .collect();
+
+                #construct_variant
             }
-        },
-        Some(default) => quote_use! {
-            # use core::result::Result::Ok;
-            # use from_attr::ConvertParsed;
+        });
 
-            #field_ident: parser.#field_ident
-                .map(ConvertParsed::convert)
-                .unwrap_or_else(|| Ok(#default))?
-        },
-    });
+        variant_keys.push(variant_key);
+    }
 
-    defined_fields.insert(field_ident, rename_field_ident);
+    if let Some(e) = errors.into_iter().reduce(|mut a, b| {
+        a.combine(b);
+        a
+    }) {
+        return Err(e);
+    }
+
+    if keys.iter().any(|(key, _)| *key == tag) {
+        return Err(syn::Error::new(
+            tag_span,
+            format!("the tag `{tag}` conflicts with a field of the variants"),
+        ));
+    }
+
+    let parse_arguments = keys.iter().map(|(key, variant_idents)| {
+        quote_use! {
+            # use core::clone::Clone;
+            # use core::option::Option::{Some, None};
+            # use core::result::Result::{Ok, Err};
+            # use from_attr::__internal::{parse_meta_speculatively, skip_token_trees};
+
+            if meta_path.is_ident(#key) {
+                self.keys.push((#key, meta_path_span));
+
+                // the tag may not be known yet, so every variant with this key
+                // parses it, errors are only reported for the selected variant
+                let mut consumed = None;
+                let mut first_err = None;
+
+                #(
+                    match parse_meta_speculatively(&mut self.#variant_idents.0, &meta) {
+                        Ok(n) => {
+                            consumed.get_or_insert(n);
+                        }
+                        Err(err) => {
+                            first_err.get_or_insert_with(|| Clone::clone(&err));
+                            self.#variant_idents.1.push(err);
+                        }
+                    }
+                )*
+
+                return match consumed {
+                    Some(n) => Ok(skip_token_trees(meta.input, n)?),
+                    None => Err(first_err.expect("at least one variant accepts the key")),
+                };
+            }
+        }
+    });
+
+    let (key_names, key_aliases): (Vec<_>, Vec<_>) = keys
+        .iter()
+        .map(|(key, _)| key)
+        .partition(|key| field_names.contains(*key));
+
+    let missing_tag_msg = format!("missing `{tag}` field, try `{tag} = ...`");
+
+    let expand = quote_use! {
+        # use core::default::Default;
+        # use core::option::Option::{self, Some};
+        # use core::iter::{IntoIterator, Iterator};
+        # use core::result::Result::{Ok, Err};
+        # use std::format;
+        # use std::string::ToString;
+        # use std::vec::Vec;
+        # use from_attr::__internal::syn::{self, LitStr, meta::ParseNestedMeta, spanned::Spanned};
+        # use from_attr::__internal::proc_macro2::Span;
+        # use from_attr::__internal::{did_you_mean, join_names, unknown_field_error, unknown_field_msg};
+        # use from_attr::{
+            AttributeIdent, Diagnostic, Diagnostics, Error, ErrorKind, FromAttr, ParseMeta, PathValue,
+        };
+
+        #(#variant_parsers)*
+
+        #[doc(hidden)]
+        #[allow(non_camel_case_types, non_snake_case)]
+        #[derive(Default)]
+        #vis struct #parser_struct_ident {
+            tag: Option<PathValue<LitStr>>,
+            keys: Vec<(&'static str, Span)>,
+            #fields
+        }
+
+        #[automatically_derived]
+        impl ParseMeta for #parser_struct_ident {
+            const FIELDS: &'static [&'static str] = &[#tag, #(#key_names),*];
+
+            fn is_unit() -> bool {
+                false
+            }
+
+            fn is_field(path: &syn::Path) -> bool {
+                Self::FIELDS.iter().any(|field| path.is_ident(field))
+                    #(|| path.is_ident(#key_aliases))*
+            }
+
+            fn parse_meta(&mut self, meta: ParseNestedMeta) -> Result<(), Error> {
+                let meta_path = &meta.path;
+                let meta_path_span = Spanned::span(meta_path);
+
+                if meta_path.is_ident(#tag) {
+                    if let Some(PathValue { path, .. }) = &self.tag {
+                        return Err(Error::new(
+                            ErrorKind::DuplicateField { name: ToString::to_string(#tag), first: *path },
+                            Diagnostic::new(meta_path_span, concat!("duplicate `", #tag, "` argument"))
+                                .note(*path, "first defined here"),
+                        ));
+                    }
+
+                    let value: LitStr = meta.value()?.parse()?;
+                    let variant_keys: &[&str] = &[#(#variant_keys),*];
+
+                    if !variant_keys.contains(&value.value().as_str()) {
+                        let msg = match did_you_mean(&value.value(), variant_keys) {
+                            Some(key) => format!("unknown {} `{}`, did you mean `{}`?", #tag, value.value(), key),
+                            None => format!("unknown {} `{}`, expected one of: {}", #tag, value.value(), join_names(variant_keys)),
+                        };
+
+                        return Err(Error::new(
+                            ErrorKind::Conversion { name: Some(ToString::to_string(#tag)) },
+                            syn::Error::new(value.span(), msg),
+                        ));
+                    }
+
+                    self.tag = Some(PathValue { path: meta_path_span, value });
+                    return Ok(());
+                }
+
+                #(#parse_arguments)*
+
+                Err(unknown_field_error(&meta, unknown_field_msg(meta_path, Self::FIELDS)))
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics FromAttr for #enum_ident #ty_generics #where_clause {
+            type Parser = #parser_struct_ident;
+
+            fn from_parser(parser: Self::Parser, spans: &[Span]) -> Result<Self, Error> {
+                fn check_errors(errors: impl IntoIterator<Item = Error>) -> Result<(), Error> {
+                    errors.into_iter().collect::<Diagnostics>().finish(())
+                }
+
+                let Some(PathValue { value: tag, .. }) = &parser.tag else {
+                    let kind = ErrorKind::MissingField { name: ToString::to_string(#tag) };
+
+                    return Err(Diagnostics::spanned(spans, kind, #missing_tag_msg)
+                        .into_error()
+                        .expect("`spans` is not empty"));
+                };
+
+                match tag.value().as_str() {
+                    #(#construct_variants)*
+                    _ => unreachable!("the tag has been checked while parsing"),
+                }
+            }
+        }
+    };
+
+    Ok(expand)
+}
+
+/// The pieces generated from the fields of a struct or an enum variant.
+#[derive(Default)]
+struct GeneratedFields {
+    names: Vec<String>,
+    aliases: Vec<String>,
+    // every name, alias and negation with the field it belongs to
+    keys: HashMap<String, Ident>,
+    flattened: Vec<Type>,
+    flatten_fields: Vec<Ident>,
+    rest: Option<Ident>,
+    merges: bool,
+    locals: Vec<Ident>,
+    fields: Punctuated<TokenStream, Token![,]>,
+    parse_arguments: Vec<TokenStream>,
+    flatten_arguments: Vec<TokenStream>,
+    positional_arms: Vec<TokenStream>,
+    field_spans: Vec<TokenStream>,
+    relations_check: Vec<TokenStream>,
+    flattened_names: Vec<String>,
+    merge_checks: Vec<TokenStream>,
+    field_values: Vec<TokenStream>,
+    given_locals: Vec<Ident>,
+    dependent_values: Vec<TokenStream>,
+    condition_checks: Vec<TokenStream>,
+    members: Punctuated<TokenStream, Token![,]>,
+}
+
+/// The relations between the fields, generated once all fields are known.
+#[derive(Default)]
+struct Relations {
+    defined_fields: HashMap<Ident, String>,
+    conflicts: HashSet<(Ident, Ident)>,
+    requires: Vec<(Ident, Ident)>,
+    conditions: Vec<(Ident, ConditionKind, Condition)>,
+}
+
+#[derive(Clone, Copy)]
+enum ConditionKind {
+    Required,
+    Forbidden,
+}
+
+impl GeneratedFields {
+    fn parse(
+        fields_to_parse: Punctuated<Field, Token![,]>,
+        groups: &[ArgGroup],
+        rename_all: Option<RenameRule>,
+        merge: MergeStrategy,
+        default: bool,
+    ) -> syn::Result<Self> {
+        let mut generated = Self::default();
+        let mut relations = Relations::default();
+
+        for (index, field) in fields_to_parse.into_iter().enumerate() {
+            handle_single_field(
+                index,
+                field,
+                rename_all,
+                merge,
+                default,
+                &mut generated,
+                &mut relations,
+            )?;
+        }
+
+        let Relations {
+            defined_fields,
+            conflicts,
+            requires,
+            conditions,
+        } = relations;
+
+        let has_flattened = !generated.flattened.is_empty();
+
+        // names that are not fields of this struct must be fields of the flattened fields,
+        // which can only be checked at compile time
+        let mut errors = Vec::new();
+
+        for field_ident in conflicts
+            .iter()
+            .flat_map(|(a, b)| [a, b])
+            .chain(requires.iter().map(|(_, b)| b))
+            .chain(groups.iter().flat_map(|group| &group.idents))
+        {
+            if defined_fields.contains_key(field_ident) {
+                continue;
+            }
+
+            let name = field_ident.to_string();
+
+            if has_flattened {
+                generated.flattened_names.push(name);
+            } else {
+                errors.push(syn::Error::new(
+                    field_ident.span(),
+                    format!("unknown field `{name}`"),
+                ));
+            }
+        }
+
+        if let Some(e) = errors.into_iter().reduce(|mut a, b| {
+            a.combine(b);
+            a
+        }) {
+            return Err(e);
+        }
+
+        // the span of the path of a field, fields not defined on this struct
+        // are looked up in the flattened fields
+        let field_span = |field_ident: &Ident| -> Option<(String, TokenStream)> {
+            match defined_fields.get(field_ident) {
+                Some(rename) => Some((
+                    rename.clone(),
+                    quote_use! {
+                        parser.#field_ident.iter().next().map(|(a, _)| a.path)
+                    },
+                )),
+                None if has_flattened => {
+                    let name = field_ident.to_string();
+
+                    Some((
+                        name.clone(),
+                        quote_use! {
+                            # use from_attr::ParseMeta;
+
+                            ParseMeta::field_span(&parser, #name)
+                        },
+                    ))
+                }
+                None => None,
+            }
+        };
+
+        generated.relations_check = conflicts
+            .into_iter()
+            .flat_map(|(a, b)| -> Option<_> {
+                let (a_rename, a_span) = field_span(&a)?;
+                let (b_rename, b_span) = field_span(&b)?;
+                let err_msg =
+                    format!("`{a_rename}` conflicts with mutually exclusive `{b_rename}`");
+                let note_msg = format!("`{b_rename}` is given here");
+
+                Some(quote_use! {
+                    # use std::string::ToString;
+                    # use from_attr::{Diagnostic, Error, ErrorKind};
+
+                    if let (Some(a), Some(b)) = (#a_span, #b_span) {
+                        errors.push(Error::new(
+                            ErrorKind::Conflict {
+                                name: ToString::to_string(#a_rename),
+                                other: ToString::to_string(#b_rename),
+                                other_span: b,
+                            },
+                            Diagnostic::new(a, #err_msg).note(b, #note_msg),
+                        ));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        generated
+            .relations_check
+            .extend(requires.into_iter().flat_map(|(a, b)| -> Option<_> {
+                let (a_rename, a_span) = field_span(&a)?;
+                let (b_rename, b_span) = field_span(&b)?;
+                let err_msg = format!("`{a_rename}` requires `{b_rename}`");
+
+                Some(quote_use! {
+                    # use core::option::Option::{Some, None};
+                    # use std::string::ToString;
+                    # use from_attr::{Error, ErrorKind};
+                    # use from_attr::__internal::syn;
+
+                    if let (Some(a), None) = (#a_span, #b_span) {
+                        errors.push(Error::new(
+                            ErrorKind::Requires {
+                                name: ToString::to_string(#a_rename),
+                                required: ToString::to_string(#b_rename),
+                            },
+                            syn::Error::new(a, #err_msg),
+                        ));
+                    }
+                })
+            }));
+
+        let mut condition_errors = Vec::new();
+
+        for (field_ident, kind, Condition { ident, value }) in conditions {
+            let Some(trigger_rename) = defined_fields.get(&ident) else {
+                condition_errors.push(syn::Error::new(
+                    ident.span(),
+                    format!("unknown field `{ident}`"),
+                ));
+                continue;
+            };
+
+            let (rename, span) = field_span(&field_ident).expect("the field is defined");
+            let (_, trigger_span) = field_span(&ident).expect("the field is defined");
+            let trigger_local = field_local(&ident);
+
+            let span_local = format_ident!("path_{field_ident}");
+            let trigger_span_local = format_ident!("path_{ident}");
+
+            let condition = format!(
+                "`{trigger_rename} = {}`",
+                quote::ToTokens::to_token_stream(&value)
+                    .to_string()
+                    .replace(' ', "")
+            );
+
+            generated.relations_check.push(quote_use! {
+                let #span_local = #span;
+                let #trigger_span_local = #trigger_span;
+            });
+
+            let check = match kind {
+                ConditionKind::Required => {
+                    let msg = format!("missing `{rename}` field, required when {condition}");
+
+                    quote_use! {
+                        # use core::option::Option::{Some, None};
+                        # use std::slice;
+                        # use std::string::ToString;
+                        # use from_attr::{Diagnostics, ErrorKind};
+
+                        if #span_local.is_none() {
+                            let trigger_spans = match &#trigger_span_local {
+                                Some(span) => slice::from_ref(span),
+                                None => spans,
+                            };
+                            let kind = ErrorKind::MissingField { name: ToString::to_string(#rename) };
+
+                            errors.extend(Diagnostics::spanned(trigger_spans, kind, #msg).into_error());
+                        }
+                    }
+                }
+                ConditionKind::Forbidden => {
+                    let msg = format!("`{rename}` cannot be given when {condition}");
+
+                    quote_use! {
+                        # use core::option::Option::Some;
+                        # use std::string::ToString;
+                        # use from_attr::{Error, ErrorKind};
+                        # use from_attr::__internal::syn;
+
+                        if let Some(span) = #span_local {
+                            errors.push(Error::new(
+                                ErrorKind::Conflict {
+                                    name: ToString::to_string(#rename),
+                                    other: ToString::to_string(#trigger_rename),
+                                    other_span: #trigger_span_local.unwrap_or(span),
+                                },
+                                syn::Error::new(span, #msg),
+                            ));
+                        }
+                    }
+                }
+            };
+
+            // the comparison is spanned on the value, so that a type mismatch or a missing
+            // `PartialEq` is reported there
+            let is_triggered = quote_spanned!(value.span()=> #trigger_local == #value);
+
+            generated.condition_checks.push(quote_use! {
+                if #is_triggered {
+                    #check
+                }
+            });
+        }
+
+        if let Some(e) = condition_errors.into_iter().reduce(|mut a, b| {
+            a.combine(b);
+            a
+        }) {
+            return Err(e);
+        }
+
+        for ArgGroup { kind, idents, .. } in groups {
+            let (renames, spans): (Vec<_>, Vec<_>) = idents.iter().flat_map(&field_span).unzip();
+
+            let names = renames
+                .iter()
+                .map(|a| format!("`{}`", a))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let missing_msg = match kind {
+                ArgGroupKind::OneOf => format!("missing one of: {names}"),
+                ArgGroupKind::AnyOf => format!("missing at least one of: {names}"),
+            };
+
+            let too_many_check = match kind {
+                ArgGroupKind::OneOf => {
+                    let too_many_msg = format!("expected only one of: {names}");
+
+                    Some(quote_use! {
+                        # use from_attr::Diagnostics;
+
+                        if given.len() > 1 {
+                            let kind = group_kind();
+                            errors.extend(Diagnostics::spanned(&given, kind, #too_many_msg).into_error());
+                        }
+                    })
+                }
+                ArgGroupKind::AnyOf => None,
+            };
+
+            generated.relations_check.push(quote_use! {
+                # use core::iter::{IntoIterator, Iterator};
+                # use std::string::ToString;
+                # use std::vec::Vec;
+                # use from_attr::{Diagnostics, ErrorKind};
+                # use from_attr::__internal::proc_macro2::Span;
+
+                {
+                    let group_kind = || ErrorKind::Group {
+                        names: [#(#renames),*].into_iter().map(ToString::to_string).collect(),
+                    };
+                    let given: Vec<Span> = [#(#spans),*].into_iter().flatten().collect();
+
+                    if given.is_empty() {
+                        errors.extend(Diagnostics::spanned(spans, group_kind(), #missing_msg).into_error());
+                    }
+
+                    #too_many_check
+                }
+            });
+        }
+
+        Ok(generated)
+    }
+
+    /// Generate the parser struct and its [`ParseMeta`] implementation.
+    fn parser_struct(
+        &self,
+        vis: &Visibility,
+        parser_struct_ident: &Ident,
+        allow_unknown: bool,
+    ) -> TokenStream {
+        let Self {
+            names,
+            aliases,
+            flattened,
+            flatten_fields,
+            rest,
+            merges,
+            fields,
+            parse_arguments,
+            flatten_arguments,
+            positional_arms,
+            field_spans,
+            flattened_names,
+            merge_checks,
+            ..
+        } = self;
+
+        let name_checks = flattened_names.iter().map(|name| {
+            let msg = format!("unknown field `{name}`");
+
+            quote_use! {
+                # use from_attr::ParseMeta;
+                # use from_attr::__internal::contains_field;
+
+                const _: () = assert!(
+                    contains_field(<#parser_struct_ident as ParseMeta>::FIELDS, #name),
+                    #msg
+                );
+            }
+        });
+
+        // `FIELDS` is the concatenation of the own fields and the fields of every flattened field
+        let all_fields = {
+            let mut all_fields = quote_use! {
+                const FIELDS_0: &[&str] = &[#(#names),*];
+            };
+
+            for (i, ty) in flattened.iter().enumerate() {
+                let prev = format_ident!("FIELDS_{i}");
+                let inner = format_ident!("INNER_FIELDS_{}", i + 1);
+                let next = format_ident!("FIELDS_{}", i + 1);
+
+                all_fields.extend(quote_use! {
+                    # use from_attr::{FromAttr, ParseMeta};
+                    # use from_attr::__internal::concat_fields;
+
+                    const #inner: &[&str] = <<#ty as FromAttr>::Parser as ParseMeta>::FIELDS;
+                    const #next: &[&str] = &concat_fields::<{ #prev.len() + #inner.len() }>(#prev, #inner);
+                });
+            }
+
+            let last = format_ident!("FIELDS_{}", flattened.len());
+
+            quote_use! {
+                {
+                    #all_fields
+                    #last
+                }
+            }
+        };
+
+        // the keys given in the current attribute, only needed to merge keys across attributes
+        let attr_keys = merges.then(|| {
+            quote_use! {
+                # use std::vec::Vec;
+                # use from_attr::__internal::proc_macro2::Span;
+
+                __attr_keys: Vec<(&'static str, Span)>,
+            }
+        });
+
+        let start_attribute = (*merges || !flattened.is_empty()).then(|| {
+            let clear_keys = merges.then(|| quote_use!(self.__attr_keys.clear();));
+            let flattened_fields = flatten_fields.iter();
+
+            quote_use! {
+                # use from_attr::ParseMeta;
+
+                fn start_attribute(&mut self) {
+                    #clear_keys
+                    #(ParseMeta::start_attribute(&mut self.#flattened_fields);)*
+                }
+            }
+        });
+
+        let unknown_argument = match rest {
+            Some(rest) => quote_use! {
+                # use core::result::Result::Ok;
+                # use from_attr::__internal::parse_unknown_meta;
+
+                self.#rest.push(parse_unknown_meta(&meta)?);
+                Ok(())
+            },
+            None if allow_unknown => quote_use! {
+                # use core::result::Result::Ok;
+                # use from_attr::__internal::parse_unknown_meta;
+
+                parse_unknown_meta(&meta)?;
+                Ok(())
+            },
+            None => quote_use! {
+                # use core::result::Result::Err;
+                # use from_attr::__internal::{unknown_field_error, unknown_field_msg};
+
+                Err(unknown_field_error(&meta, unknown_field_msg(meta_path, Self::FIELDS)))
+            },
+        };
+
+        let positional = if positional_arms.is_empty() {
+            None
+        } else {
+            let too_many_msg = format!(
+                "unexpected positional argument, expected at most {}",
+                positional_arms.len()
+            );
+
+            Some(quote_use! {
+                # use core::result::Result::{Ok, Err};
+                # use from_attr::Error;
+                # use from_attr::__internal::is_named_argument;
+                # use from_attr::__internal::syn::{self, Token, parse::ParseStream};
+
+                fn has_positional() -> bool {
+                    true
+                }
+
+                fn parse_positional(&mut self, input: ParseStream) -> Result<(), Error> {
+                    let mut index = 0usize;
+
+                    while !input.is_empty() && !is_named_argument(input, Self::is_field) {
+                        let span = input.span();
+
+                        match index {
+                            #(#positional_arms)*
+                            _ => return Err(syn::Error::new(span, #too_many_msg).into()),
+                        }
+
+                        index += 1;
+
+                        if !input.is_empty() {
+                            input.parse::<Token![,]>()?;
+                        }
+                    }
+
+                    Ok(())
+                }
+            })
+        };
+
+        quote_use! {
+            # use core::default::Default;
+            # use core::option::Option::{self, None};
+            # use from_attr::__internal::syn::{self, Token, meta::ParseNestedMeta, spanned::Spanned};
+            # use from_attr::__internal::proc_macro2::Span;
+            # use core::iter::Iterator;
+            # use from_attr::{Error, FromAttr, ParseMeta};
+
+            #[doc(hidden)]
+            #[allow(non_camel_case_types)]
+            #[derive(Default)]
+            #vis struct #parser_struct_ident {
+                #attr_keys
+                #fields
+            }
+
+            #(#name_checks)*
+            #(#merge_checks)*
+
+            #[automatically_derived]
+            impl ParseMeta for #parser_struct_ident {
+                const FIELDS: &'static [&'static str] = #all_fields;
+
+                fn is_unit() -> bool {
+                    false
+                }
+
+                fn is_field(path: &syn::Path) -> bool {
+                    Self::FIELDS.iter().any(|field| path.is_ident(field))
+                        #(|| path.is_ident(#aliases))*
+                        #(|| <<#flattened as FromAttr>::Parser as ParseMeta>::is_field(path))*
+                }
+
+                #positional
+
+                fn parse_meta(&mut self, meta: ParseNestedMeta) -> Result<(), Error> {
+                    let meta_path = &meta.path;
+                    let meta_path_span = Spanned::span(meta_path);
+
+                    let next_token_is_not_eq = !meta.input.peek(Token![=]);
+
+                    #(#parse_arguments)*
+
+                    #(#flatten_arguments)*
+
+                    #unknown_argument
+                }
+
+                #start_attribute
+
+                fn field_span(&self, name: &str) -> Option<Span> {
+                    #(#field_spans)*
+                    None
+                }
+            }
+        }
+    }
+}
+
+fn handle_single_field(
+    index: usize,
+    field: Field,
+    rename_all: Option<RenameRule>,
+    merge: MergeStrategy,
+    struct_default: bool,
+    generated: &mut GeneratedFields,
+    relations: &mut Relations,
+) -> syn::Result<()> {
+    let Field {
+        attrs, ident, ty, ..
+    } = field;
+
+    let FieldAttr {
+        rename,
+        alias,
+        default,
+        default_with,
+        skip,
+        conflicts: current_conflicts,
+        requires: current_requires,
+        required,
+        required_if,
+        forbidden_if,
+        positional,
+        multiple,
+        negatable,
+        flag,
+        merge: field_merge,
+        flatten,
+        rest,
+        validate,
+        range,
+        non_empty,
+        max_len,
+        unique,
+        pattern,
+    } = FieldAttr::parse_attrs(&attrs)?.unwrap_or_default();
+
+    let member = match &ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(Index::from(index)),
+    };
+
+    // the name in the errors, the index of an unnamed field
+    let name = match &ident {
+        Some(ident) => field_key(ident, rename.as_deref(), rename_all),
+        None => index.to_string(),
+    };
+
+    let subject = match &ident {
+        Some(_) => format!("`{name}`"),
+        None => format!("positional argument {index}"),
+    };
+
+    let checks = builtin_checks(
+        &name, &subject, &ty, range, non_empty, max_len, unique, pattern,
+    );
+
+    if default_with.is_some() && !matches!(default, FlagOrValue::None) {
+        return Err(syn::Error::new_spanned(
+            ty,
+            "`default` and `default_with` cannot be used together",
+        ));
+    }
+
+    // `default_with` is called with references to the listed fields, declared before this field
+    let default_with: Option<Expr> = match default_with {
+        Some(DefaultWith { function, inputs }) => {
+            let mut locals = Vec::new();
+
+            for input in inputs {
+                let local = field_local(&input);
+
+                if !generated.locals.contains(&local) {
+                    return Err(syn::Error::new(
+                        input.span(),
+                        format!("unknown field `{input}`, `default_with` can only use the fields declared before"),
+                    ));
+                }
+
+                locals.push(local);
+            }
+
+            Some(parse_quote!((#function)(#(&#locals),*)))
+        }
+        None => None,
+    };
+
+    // the `default` of the struct only applies to the parsed fields without a default
+    let default = if struct_default
+        && !required
+        && !flatten
+        && !rest
+        && default_with.is_none()
+        && matches!(default, FlagOrValue::None)
+    {
+        FlagOrValue::Flag
+    } else {
+        default
+    };
+
+    if required && (default_with.is_some() || !matches!(default, FlagOrValue::None)) {
+        return Err(syn::Error::new_spanned(
+            ty,
+            "`required` and `default` cannot be used together",
+        ));
+    }
+
+    if skip {
+        if rename.is_some()
+            || !alias.is_empty()
+            || !current_conflicts.is_empty()
+            || !current_requires.is_empty()
+            || required
+            || required_if.is_some()
+            || forbidden_if.is_some()
+            || positional
+            || multiple
+            || negatable
+            || flag.is_some()
+            || field_merge.is_some()
+            || flatten
+            || rest
+            || validate.is_some()
+            || !checks.is_empty()
+        {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "`skip` can only be used with `default` and `default_with`",
+            ));
+        }
+
+        let default: Expr = match (default_with, default) {
+            (Some(expr), _) | (None, FlagOrValue::Value(expr)) => expr,
+            (None, FlagOrValue::None | FlagOrValue::Flag) => {
+                parse_quote!(::core::default::Default::default())
+            }
+        };
+
+        let field_ident = ident.unwrap_or_else(|| format_ident!("_{index}"));
+        let local = field_local(&field_ident);
+
+        generated.dependent_values.push(quote_use! {
+            let #local: #ty = #default;
+        });
+
+        generated.members.push(quote_use!(#member: #local));
+        generated.locals.push(local);
+
+        return Ok(());
+    }
+
+    if flatten {
+        if rename.is_some()
+            || !alias.is_empty()
+            || rest
+            || default_with.is_some()
+            || !matches!(default, FlagOrValue::None)
+            || !current_conflicts.is_empty()
+            || !current_requires.is_empty()
+            || required
+            || required_if.is_some()
+            || forbidden_if.is_some()
+            || positional
+            || multiple
+            || negatable
+            || flag.is_some()
+            || field_merge.is_some()
+            || validate.is_some()
+            || !checks.is_empty()
+        {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "`flatten` cannot be used with other arguments",
+            ));
+        }
+
+        let field_ident = ident.unwrap_or_else(|| format_ident!("_{index}"));
+
+        generated.fields.push(quote_use! {
+            # use from_attr::FromAttr;
+
+            #field_ident: <#ty as FromAttr>::Parser
+        });
+
+        generated.flatten_arguments.push(quote_use! {
+            # use core::iter::Iterator;
+            # use from_attr::{FromAttr, ParseMeta};
+
+            if <<#ty as FromAttr>::Parser as ParseMeta>::is_field(meta_path) {
+                return ParseMeta::parse_meta(&mut self.#field_ident, meta);
+            }
+        });
+
+        generated.field_spans.push(quote_use! {
+            # use core::option::Option::Some;
+            # use from_attr::ParseMeta;
+
+            if let Some(span) = ParseMeta::field_span(&self.#field_ident, name) {
+                return Some(span);
+            }
+        });
+
+        let local = field_local(&field_ident);
+
+        generated.field_values.push(collect_error(
+            &local,
+            &ty,
+            quote_use! {
+                # use from_attr::FromAttr;
+
+                <#ty as FromAttr>::from_parser(parser.#field_ident, spans)
+            },
+        ));
+
+        generated.members.push(quote_use!(#member: #local));
+        generated.locals.push(local.clone());
+        generated.given_locals.push(local);
+
+        generated.flattened.push(ty);
+        generated.flatten_fields.push(field_ident);
+
+        return Ok(());
+    }
+
+    if rest {
+        if rename.is_some()
+            || !alias.is_empty()
+            || default_with.is_some()
+            || !matches!(default, FlagOrValue::None)
+            || !current_conflicts.is_empty()
+            || !current_requires.is_empty()
+            || required
+            || required_if.is_some()
+            || forbidden_if.is_some()
+            || positional
+            || multiple
+            || negatable
+            || flag.is_some()
+            || field_merge.is_some()
+            || validate.is_some()
+            || !checks.is_empty()
+        {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "`rest` cannot be used with other arguments",
+            ));
+        }
+
+        let Some(field_ident) = ident else {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "`rest` cannot be used on unnamed fields",
+            ));
+        };
+
+        if generated.rest.is_some() {
+            return Err(syn::Error::new_spanned(ty, "only one field can be `rest`"));
+        }
+
+        generated.fields.push(quote_use! {
+            # use std::vec::Vec;
+            # use from_attr::__internal::syn::Meta;
+
+            #field_ident: Vec<Meta>
+        });
+
+        let local = field_local(&field_ident);
+
+        generated.field_values.push(collect_error(
+            &local,
+            &ty,
+            quote_use! {
+                # use from_attr::FromRest;
+
+                <#ty as FromRest>::from_rest(parser.#field_ident)
+            },
+        ));
+
+        generated.members.push(quote_use!(#member: #local));
+        generated.locals.push(local.clone());
+        generated.given_locals.push(local);
+
+        generated.rest = Some(field_ident);
+
+        return Ok(());
+    }
+
+    if multiple && field_merge.is_some() {
+        return Err(syn::Error::new_spanned(
+            ty,
+            "`merge` cannot be used with `multiple`, the values are always appended",
+        ));
+    }
+
+    // `multiple` fields are always appended
+    let merge = if multiple {
+        MergeStrategy::Error
+    } else {
+        field_merge.unwrap_or(merge)
+    };
+
+    generated.merges |= merge != MergeStrategy::Error;
+
+    if merge == MergeStrategy::Append {
+        let msg = format!("`{name}` cannot be appended, `merge = \"append\"` needs a collection");
+
+        // spanned on the type, where the error is reported
+        generated.merge_checks.push(quote_spanned_use! {ty.span()=>
+            # use from_attr::ConvertParsed;
+
+            const _: () = assert!(<#ty as ConvertParsed>::APPENDABLE, #msg);
+        });
+    }
+
+    // fields of tuple structs can only be given as positional arguments
+    let Some(field_ident) = ident else {
+        if rename.is_some()
+            || !alias.is_empty()
+            || !current_conflicts.is_empty()
+            || !current_requires.is_empty()
+            || required_if.is_some()
+            || forbidden_if.is_some()
+            || multiple
+            || negatable
+            || flag.is_some()
+        {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "`rename`, `alias`, `conflicts`, `requires`, `required_if`, `forbidden_if`, `multiple`, `negatable` and `flag` cannot be used on unnamed fields",
+            ));
+        }
+
+        let field_ident = format_ident!("_{index}");
+
+        let key = format!("positional argument {index}");
+        let missing_msg = format!("missing {key}");
+        let duplicate_msg = format!("duplicate {key}");
+
+        generated.fields.push(quote_use! {
+            # use core::option::Option;
+            # use from_attr::{ConvertParsed, PathValue};
+            # use from_attr::__internal::proc_macro2::Span;
+
+            #field_ident: Option<(PathValue<<#ty as ConvertParsed>::Type>, Span)>
+        });
+
+        generated.positional_arms.push(positional_arm(
+            generated.positional_arms.len(),
+            &field_ident,
+            &ty,
+            &key,
+            &duplicate_msg,
+            merge,
+            false,
+        ));
+
+        let local = field_local(&field_ident);
+
+        generated.members.push(quote_use!(#member: #local));
+        generated.locals.push(local.clone());
+        generated.field_values.push(field_value(
+            &local,
+            &field_ident,
+            &ty,
+            &name,
+            false,
+            None,
+            default,
+            default_with.is_some(),
+            required,
+            validate.as_ref(),
+            checks,
+            &missing_msg,
+            &missing_msg,
+        ));
+        generated.given_locals.push(local.clone());
+        generated
+            .dependent_values
+            .extend(default_with_value(&local, &ty, default_with));
+
+        return Ok(());
+    };
+
+    let is_bool = matches!(
+        &ty,
+        Type::Path(TypePath { qself: None, path })
+            if path.segments.last().is_some_and(|segment| segment.ident == "bool")
+    );
+
+    if negatable && !is_bool {
+        return Err(syn::Error::new_spanned(
+            ty,
+            "`negatable` requires a `bool` field",
+        ));
+    }
+
+    if multiple && (negatable || flag.is_some()) {
+        return Err(syn::Error::new_spanned(
+            ty,
+            "`negatable` and `flag` cannot be used with `multiple`",
+        ));
+    }
+
+    if flag.is_some() && merge == MergeStrategy::Append {
+        return Err(syn::Error::new_spanned(
+            ty,
+            "`flag` cannot be used with `merge = \"append\"`",
+        ));
+    }
+
+    if positional && multiple {
+        return Err(syn::Error::new_spanned(
+            ty,
+            "`positional` and `multiple` cannot be used together",
+        ));
+    }
+
+    let rename_field_ident = field_key(&field_ident, rename.as_deref(), rename_all);
+
+    for conflict_field_ident in current_conflicts {
+        if conflict_field_ident < field_ident {
+            relations
+                .conflicts
+                .insert((conflict_field_ident, field_ident.clone()));
+        } else {
+            relations
+                .conflicts
+                .insert((field_ident.clone(), conflict_field_ident));
+        }
+    }
+
+    for required_field_ident in current_requires {
+        relations
+            .requires
+            .push((field_ident.clone(), required_field_ident));
+    }
+
+    if let Some(condition) = required_if {
+        relations
+            .conditions
+            .push((field_ident.clone(), ConditionKind::Required, condition));
+    }
+
+    if let Some(condition) = forbidden_if {
+        relations
+            .conditions
+            .push((field_ident.clone(), ConditionKind::Forbidden, condition));
+    }
+
+    if multiple {
+        // every element is kept with its own value span
+        generated.fields.push(quote_use! {
+            # use std::vec::Vec;
+            # use from_attr::{Multiple, PathValue};
+            # use from_attr::__internal::proc_macro2::Span;
+
+            #field_ident: Vec<(PathValue<<#ty as Multiple>::Item>, Span)>
+        });
+
+        generated.parse_arguments.push(quote_use! {
+            # use core::iter::Iterator;
+            # use from_attr::{Multiple, PathValue};
+
+            if meta_path.is_ident(#rename_field_ident) #(|| meta_path.is_ident(#alias))* {
+                let items = <#ty as Multiple>::parse_items(meta.value()?)?;
+
+                self.#field_ident.extend(items.into_iter().map(|(value, value_span)| {
+                    (PathValue { path: meta_path_span, value }, value_span)
+                }));
+                return Ok(());
+            }
+        });
+    } else {
+        // with `flag = ...`, a bare flag is stored as `None`, its value is built once converting
+        let stored_ty = match flag {
+            Some(_) => quote_use! {
+                # use core::option::Option;
+                # use from_attr::ConvertParsed;
+
+                Option<<#ty as ConvertParsed>::Type>
+            },
+            None => quote_use! {
+                # use from_attr::ConvertParsed;
+
+                <#ty as ConvertParsed>::Type
+            },
+        };
+
+        generated.fields.push(quote_use! {
+            # use core::option::Option;
+            # use from_attr::PathValue;
+            # use from_attr::__internal::proc_macro2::Span;
+
+            #field_ident: Option<(PathValue<#stored_ty>, Span)>
+        });
+    }
+
+    let duplicate_msg = format!("duplicate `{rename_field_ident}` argument");
+
+    if positional {
+        generated.positional_arms.push(positional_arm(
+            generated.positional_arms.len(),
+            &field_ident,
+            &ty,
+            &rename_field_ident,
+            &duplicate_msg,
+            merge,
+            flag.is_some(),
+        ));
+    }
+
+    let (check, store) = merge_value(
+        &field_ident,
+        &ty,
+        &rename_field_ident,
+        &duplicate_msg,
+        merge,
+        quote_use!(meta_path_span),
+    );
+
+    // the value of a bare flag, with `flag = ...` it is built once converting
+    let flag_arm = match flag {
+        Some(_) => quote_use! {
+            # use core::option::Option::None;
+
+            else if next_token_is_not_eq {
+                (None, meta_path_span)
+            }
+        },
+        None => quote_use! {
+            # use core::option::Option::Some;
+            # use from_attr::ConvertParsed;
+
+            else if let Some(value) = next_token_is_not_eq.then(<#ty as ConvertParsed>::flag).flatten() {
+                (value, meta_path_span)
+            }
+        },
+    };
+
+    let is_flag = flag.is_some();
+    let list_value = stored_value(is_flag, quote_use!(value?));
+    // a literal of the wrong type is a conversion error of the field, at the span of the literal
+    let parsed_value = stored_value(
+        is_flag,
+        quote_use! {
+            # use std::string::ToString;
+            # use from_attr::{Error, ErrorKind};
+
+            input.parse().map_err(|err| Error::new(
+                ErrorKind::Conversion { name: Some(ToString::to_string(#rename_field_ident)) },
+                err,
+            ))?
+        },
+    );
+
+    // without `merge`, `x` and `no_x` cannot be given together, otherwise they are merged
+    let negated = negatable.then(|| {
+        let negated_key = format!("no_{rename_field_ident}");
+        let negated_field = format_ident!("__negated_{}", field_ident.unraw());
+        let negated_duplicate_msg = format!("duplicate `{negated_key}` argument");
+        let unexpected_value_msg = format!("`{negated_key}` does not take a value");
+        let to_negated_msg =
+            format!("`{rename_field_ident}` conflicts with mutually exclusive `{negated_key}`");
+        let from_negated_msg =
+            format!("`{negated_key}` conflicts with mutually exclusive `{rename_field_ident}`");
+        let given_msg = format!("`{rename_field_ident}` is given here");
+        let negated_given_msg = format!("`{negated_key}` is given here");
+        let negated_value = stored_value(is_flag, quote_use!(value));
+
+        let (negated_check, negated_store) = merge_value(
+            &field_ident,
+            &ty,
+            &negated_key,
+            &negated_duplicate_msg,
+            merge,
+            quote_use!(meta_path_span),
+        );
+
+        let conflicts = merge == MergeStrategy::Error;
+
+        if conflicts {
+            generated.fields.push(quote_use! {
+                # use core::option::Option;
+                # use from_attr::__internal::proc_macro2::Span;
+
+                #negated_field: Option<Span>
+            });
+        }
+
+        let from_negated_check = conflicts.then(|| {
+            quote_use! {
+                # use core::option::Option::{Some, None};
+                # use core::result::Result::Err;
+                # use std::string::ToString;
+                # use from_attr::{Diagnostic, Error, ErrorKind};
+
+                if let (Some((a, _)), None) = (&self.#field_ident, self.#negated_field) {
+                    return Err(Error::new(
+                        ErrorKind::Conflict {
+                            name: ToString::to_string(#negated_key),
+                            other: ToString::to_string(#rename_field_ident),
+                            other_span: a.path,
+                        },
+                        Diagnostic::new(meta_path_span, #from_negated_msg).note(a.path, #given_msg),
+                    ));
+                }
+            }
+        });
+
+        let store_negated = conflicts.then(|| {
+            quote_use! {
+                # use core::option::Option::Some;
+
+                self.#negated_field = Some(meta_path_span);
+            }
+        });
+
+        generated.parse_arguments.push(quote_use! {
+            # use core::result::Result::{Ok, Err};
+            # use from_attr::ConvertParsed;
+            # use from_attr::__internal::syn::{self, Token, token};
+
+            if meta_path.is_ident(#negated_key) {
+                if meta.input.peek(Token![=]) || meta.input.peek(token::Paren) {
+                    return Err(meta.error(#unexpected_value_msg).into());
+                }
+
+                #from_negated_check
+                #negated_check
+
+                let value: <#ty as ConvertParsed>::Type = syn::LitBool::new(false, meta_path_span);
+                let value = #negated_value;
+                let value_span = meta_path_span;
+
+                #negated_store
+                #store_negated
+                return Ok(());
+            }
+        });
+
+        generated.aliases.push(negated_key.clone());
+
+        conflicts.then(|| {
+            quote_use! {
+                # use core::option::Option::Some;
+                # use core::result::Result::Err;
+                # use std::string::ToString;
+                # use from_attr::{Diagnostic, Error, ErrorKind};
+
+                if let Some(span) = self.#negated_field {
+                    return Err(Error::new(
+                        ErrorKind::Conflict {
+                            name: ToString::to_string(#rename_field_ident),
+                            other: ToString::to_string(#negated_key),
+                            other_span: span,
+                        },
+                        Diagnostic::new(meta_path_span, #to_negated_msg).note(span, #negated_given_msg),
+                    ));
+                }
+            }
+        })
+    })
+    .flatten();
+
+    generated.parse_arguments.extend((!multiple).then(|| quote_use! {
+        # use core::option::Option::Some;
+        # use std::string::ToString;
+        # use std::format;
+        # use from_attr::{ConvertParsed, PathValue};
+        # use from_attr::merge;
+        # use from_attr::__internal::syn::{self, Token, token};
+
+        if meta_path.is_ident(#rename_field_ident) #(|| meta_path.is_ident(#alias))* {
+            #negated
+            #check
+
+            let (value, value_span) = if let Some(Some(value)) = meta.input.peek(token::Paren).then(|| <#ty as ConvertParsed>::list(&meta)) {
+                (#list_value, meta_path_span)
+            } #flag_arm else {
+                let input = meta.value()?;
+                (#parsed_value, input.span())
+            };
+
+            #store
+            return Ok(());
+        }
+    }));
+
+    generated.field_spans.push(quote_use! {
+        if name == #rename_field_ident {
+            return self.#field_ident.iter().next().map(|(a, _)| a.path);
+        }
+    });
+
+    let missing_field_msg = format!(
+        "missing `{i}` field, try `{i} = ...`",
+        i = rename_field_ident
+    );
+
+    let missing_flag_msg = format!(
+        "missing `{i}` flag, try `{i}` or `{i} = ...`",
+        i = rename_field_ident
+    );
+
+    // a field with `flag = ...` is always a flag
+    let missing_field_msg = match flag {
+        Some(_) => missing_flag_msg.clone(),
+        None => missing_field_msg,
+    };
+
+    let local = field_local(&field_ident);
+
+    generated.members.push(quote_use!(#member: #local));
+    generated.locals.push(local.clone());
+    generated.field_values.push(field_value(
+        &local,
+        &field_ident,
+        &ty,
+        &name,
+        multiple,
+        flag.as_ref(),
+        default,
+        default_with.is_some(),
+        required,
+        validate.as_ref(),
+        checks,
+        &missing_field_msg,
+        &missing_flag_msg,
+    ));
+    generated.given_locals.push(local.clone());
+    generated
+        .dependent_values
+        .extend(default_with_value(&local, &ty, default_with));
+
+    let negated_key = negatable.then(|| format!("no_{rename_field_ident}"));
+
+    for key in [&rename_field_ident]
+        .into_iter()
+        .chain(&alias)
+        .chain(&negated_key)
+    {
+        if let Some(other) = generated.keys.insert(key.clone(), field_ident.clone()) {
+            let msg = if other == field_ident {
+                format!("`{key}` is given twice as a key of `{field_ident}`")
+            } else {
+                format!("`{key}` is already a key of `{other}`")
+            };
+
+            return Err(syn::Error::new(field_ident.span(), msg));
+        }
+    }
+
+    generated.names.push(rename_field_ident.clone());
+    generated.aliases.extend(alias);
+    relations
+        .defined_fields
+        .insert(field_ident, rename_field_ident);
 
     Ok(())
 }
+
+/// The key of a named field, `rename` takes precedence over `rename_all`.
+fn field_key(field_ident: &Ident, rename: Option<&str>, rename_all: Option<RenameRule>) -> String {
+    match (rename, rename_all) {
+        (Some(rename), _) => rename.to_string(),
+        (None, Some(rename_all)) => rename_all.apply(&field_ident.unraw().to_string()),
+        (None, None) => field_ident.to_string(),
+    }
+}
+
+fn positional_arm(
+    index: usize,
+    field_ident: &Ident,
+    ty: &Type,
+    key: &str,
+    duplicate_msg: &str,
+    merge: MergeStrategy,
+    flag: bool,
+) -> TokenStream {
+    let (check, store) = merge_value(field_ident, ty, key, duplicate_msg, merge, quote_use!(span));
+    let value = stored_value(flag, quote_use!(input.parse()?));
+
+    quote_use! {
+        #index => {
+            #check
+
+            let value = #value;
+            let value_span = span;
+
+            #store
+        }
+    }
+}
+
+/// Generate the value stored in the parser, wrapped in `Some` for a field with `flag = ...`.
+fn stored_value(flag: bool, value: TokenStream) -> TokenStream {
+    if flag {
+        quote_use! {
+            # use core::option::Option::Some;
+
+            Some(#value)
+        }
+    } else {
+        value
+    }
+}
+
+/// Generate the duplicate check before a value of a field is parsed, and the statement storing
+/// `value` and `value_span` once it is parsed, according to the merge strategy.
+///
+/// Without `merge`, a key given twice is always a duplicate. Otherwise it is only a duplicate
+/// within one attribute, the keys of the current attribute are kept in `__attr_keys` with the
+/// span of their first path.
+fn merge_value(
+    field_ident: &Ident,
+    ty: &Type,
+    key: &str,
+    duplicate_msg: &str,
+    merge: MergeStrategy,
+    span: TokenStream,
+) -> (TokenStream, TokenStream) {
+    let check = if merge == MergeStrategy::Error {
+        quote_use! {
+            # use core::option::Option::Some;
+            # use core::result::Result::Err;
+            # use std::string::ToString;
+            # use from_attr::{Diagnostic, Error, ErrorKind};
+
+            if let Some((a, _)) = &self.#field_ident {
+                return Err(Error::new(
+                    ErrorKind::DuplicateField {
+                        name: ToString::to_string(#key),
+                        first: a.path,
+                    },
+                    Diagnostic::new(#span, #duplicate_msg).note(a.path, "first defined here"),
+                ));
+            }
+        }
+    } else {
+        quote_use! {
+            # use core::option::Option::Some;
+            # use core::result::Result::Err;
+            # use std::string::ToString;
+            # use from_attr::{Diagnostic, Error, ErrorKind};
+
+            if let Some(&(_, first)) = self.__attr_keys.iter().find(|(key, _)| *key == #key) {
+                return Err(Error::new(
+                    ErrorKind::DuplicateField {
+                        name: ToString::to_string(#key),
+                        first,
+                    },
+                    Diagnostic::new(#span, #duplicate_msg).note(first, "first defined here"),
+                ));
+            }
+
+            self.__attr_keys.push((#key, #span));
+        }
+    };
+
+    let store = match merge {
+        MergeStrategy::Error | MergeStrategy::Last => quote_use! {
+            # use core::option::Option::Some;
+            # use from_attr::PathValue;
+
+            self.#field_ident = Some((PathValue { path: #span, value }, value_span));
+        },
+        MergeStrategy::First => quote_use! {
+            # use core::option::Option::Some;
+            # use from_attr::PathValue;
+
+            if self.#field_ident.is_none() {
+                self.#field_ident = Some((PathValue { path: #span, value }, value_span));
+            }
+        },
+        MergeStrategy::Append => {
+            let cannot_append_msg =
+                format!("`{key}` is given in several attributes and cannot be appended");
+
+            quote_use! {
+                # use core::option::Option::{Some, None};
+                # use core::result::Result::Err;
+                # use std::string::ToString;
+                # use from_attr::{ConvertParsed, Error, ErrorKind, PathValue};
+                # use from_attr::__internal::syn;
+
+                self.#field_ident = Some(match self.#field_ident.take() {
+                    Some((a, a_span)) => match <#ty as ConvertParsed>::append(a.value, value) {
+                        Some(value) => (PathValue { path: a.path, value }, a_span),
+                        None => {
+                            return Err(Error::new(
+                                ErrorKind::DuplicateField {
+                                    name: ToString::to_string(#key),
+                                    first: a.path,
+                                },
+                                syn::Error::new(#span, #cannot_append_msg),
+                            ))
+                        }
+                    },
+                    None => (PathValue { path: #span, value }, value_span),
+                });
+            }
+        }
+    };
+
+    (check, store)
+}
+
+/// Generate the local of a field, `None` when its value cannot be converted and the error
+/// has been collected, the value of a field with `default_with` is only given or not.
+#[allow(clippy::too_many_arguments)]
+fn field_value(
+    local: &Ident,
+    field_ident: &Ident,
+    ty: &Type,
+    name: &str,
+    multiple: bool,
+    flag: Option<&Expr>,
+    default: FlagOrValue<Expr>,
+    default_with: bool,
+    required: bool,
+    validate: Option<&Expr>,
+    checks: Vec<TokenStream>,
+    missing_field_msg: &str,
+    missing_flag_msg: &str,
+) -> TokenStream {
+    let default: Option<Expr> = match default {
+        FlagOrValue::None => None,
+        FlagOrValue::Flag => Some(parse_quote!(::core::default::Default::default())),
+        FlagOrValue::Value(expr) => Some(expr),
+    };
+
+    // a required field never falls back to the default value of its type
+    let type_default = if required {
+        quote_use! {
+            # use core::option::Option::None;
+
+            None
+        }
+    } else {
+        quote_use! {
+            # use from_attr::ConvertParsed;
+
+            <#ty as ConvertParsed>::default()
+        }
+    };
+
+    // the given value, converted to `Option<Result<#ty, Error>>`
+    let converted = if multiple {
+        quote_use! {
+            # use core::iter::Iterator;
+            # use from_attr::Multiple;
+
+            (!parser.#field_ident.is_empty()).then(|| {
+                <#ty as Multiple>::from_items(parser.#field_ident.into_iter().map(|(a, _)| a).collect())
+                    .map_err(|err| err.with_name(#name))
+            })
+        }
+    } else if let Some(flag) = flag {
+        quote_use! {
+            # use core::option::Option::{Some, None};
+            # use core::result::Result::Ok;
+            # use from_attr::{ConvertParsed, PathValue};
+
+            parser.#field_ident.map(|(PathValue { path, value }, _)| match value {
+                Some(value) => <#ty as ConvertParsed>::convert(PathValue { path, value })
+                    .map_err(|err| err.with_name(#name)),
+                None => {
+                    let value: #ty = #flag;
+                    Ok(value)
+                }
+            })
+        }
+    } else {
+        quote_use! {
+            # use from_attr::ConvertParsed;
+
+            parser.#field_ident.map(|(a, _)| {
+                <#ty as ConvertParsed>::convert(a).map_err(|err| err.with_name(#name))
+            })
+        }
+    };
+
+    // the value as `Result<#ty, Error>`, or `Result<Option<#ty>, Error>` with `default_with`
+    let value = match default {
+        _ if default_with => quote_use!(#converted.transpose()),
+        None => quote_use! {
+            # use core::option::Option::{Some, None};
+            # use core::result::Result::{Ok, Err};
+            # use std::string::ToString;
+            # use from_attr::{ConvertParsed, Diagnostics, ErrorKind};
+
+            match #converted {
+                Some(result) => result,
+                None => match #type_default {
+                    Some(#field_ident) => Ok(#field_ident),
+                    None => {
+                        let msg = if <#ty as ConvertParsed>::flag().is_some() {
+                            #missing_flag_msg
+                        } else {
+                            #missing_field_msg
+                        };
+
+                        let kind = ErrorKind::MissingField {
+                            name: ToString::to_string(#name),
+                        };
+
+                        Err(Diagnostics::spanned(spans, kind, msg)
+                            .into_error()
+                            .expect("`spans` is not empty"))
+                    }
+                },
+            }
+        },
+        Some(default) => quote_use! {
+            # use core::result::Result::Ok;
+
+            #converted.unwrap_or_else(|| Ok(#default))
+        },
+    };
+
+    let (local_ty, ok_pattern, ok_value, not_given_arm) = if default_with {
+        (
+            quote_use! {
+                # use core::option::Option;
+
+                Option<#ty>
+            },
+            quote_use! {
+                # use core::option::Option::Some;
+                # use core::result::Result::Ok;
+
+                Ok(Some(value))
+            },
+            quote_use! {
+                # use core::option::Option::Some;
+
+                Some(value)
+            },
+            Some(quote_use! {
+                # use core::option::Option::{Some, None};
+                # use core::result::Result::Ok;
+
+                Ok(None) => Some(None),
+            }),
+        )
+    } else {
+        (
+            quote_use!(#ty),
+            quote_use! {
+                # use core::result::Result::Ok;
+
+                Ok(value)
+            },
+            quote_use!(value),
+            None,
+        )
+    };
+
+    let error_arm = quote_use! {
+        # use core::option::Option::None;
+        # use core::result::Result::Err;
+
+        Err(err) => {
+            errors.push(err);
+            None
+        }
+    };
+
+    if validate.is_none() && checks.is_empty() {
+        return quote_use! {
+            # use core::option::Option::{self, Some};
+
+            let #local: Option<#local_ty> = match #value {
+                #ok_pattern => Some(#ok_value),
+                #not_given_arm
+                #error_arm
+            };
+        };
+    }
+
+    let validate = validate.map(|validate| {
+        quote_use! {
+            # use core::option::Option::Some;
+            # use core::result::Result::Err;
+            # use std::string::ToString;
+            # use from_attr::{Error, ErrorKind, PathValue};
+
+            if let Err(err) = (#validate)(PathValue { path, value: &value }) {
+                let kind = ErrorKind::Validation {
+                    name: Some(ToString::to_string(#name)),
+                };
+
+                errors.push(Error::new(kind, err));
+            }
+        }
+    });
+
+    let path = match validate {
+        Some(_) => quote_use!(path),
+        None => quote_use!(_),
+    };
+
+    let value_span = if checks.is_empty() {
+        quote_use!(_)
+    } else {
+        quote_use!(value_span)
+    };
+
+    // only the values given in the attribute are validated
+    quote_use! {
+        # use core::option::Option::{self, Some};
+
+        let #local: Option<#local_ty> = {
+            let given = parser.#field_ident.iter().next().map(|(a, value_span)| (a.path, *value_span));
+
+            match #value {
+                #ok_pattern => {
+                    if let Some((#path, #value_span)) = given {
+                        #validate
+                        #(#checks)*
+                    }
+
+                    Some(#ok_value)
+                }
+                #not_given_arm
+                #error_arm
+            }
+        };
+    }
+}
+
+/// Generate the value of a field with `default_with` when it is not given,
+/// once the fields declared before it have their values.
+fn default_with_value(local: &Ident, ty: &Type, default_with: Option<Expr>) -> Option<TokenStream> {
+    let default_with = default_with?;
+
+    Some(quote_use! {
+        # use core::option::Option::{Some, None};
+
+        let #local: #ty = match #local {
+            Some(value) => value,
+            None => #default_with,
+        };
+    })
+}
+
+/// Generate the local of a field from `result`, `None` when the error has been collected.
+fn collect_error(local: &Ident, ty: &Type, result: TokenStream) -> TokenStream {
+    quote_use! {
+        # use core::option::Option::{self, Some, None};
+        # use core::result::Result::{Ok, Err};
+
+        let #local: Option<#ty> = match #result {
+            Ok(value) => Some(value),
+            Err(err) => {
+                errors.push(err);
+                None
+            }
+        };
+    }
+}
+
+/// The name of the local variable that holds the value of a field.
+fn field_local(field_ident: &Ident) -> Ident {
+    format_ident!("field_{}", field_ident.unraw())
+}
+
+/// Generate the checks of the built-in validators, they report errors on the span of the value.
+#[allow(clippy::too_many_arguments)]
+fn builtin_checks(
+    name: &str,
+    subject: &str,
+    ty: &Type,
+    range: Option<TokenStream>,
+    non_empty: bool,
+    max_len: Option<Expr>,
+    unique: bool,
+    pattern: Option<Pattern>,
+) -> Vec<TokenStream> {
+    let mut checks = Vec::new();
+
+    if let Some(range) = range {
+        let msg = format!(
+            "{subject} must be in the range {}",
+            range.to_string().replace(' ', "")
+        );
+
+        checks.push(quote_use! {
+            # use core::ops::RangeBounds;
+
+            if !RangeBounds::contains(&(#range), value) {
+                errors.push(error(value_span, #msg));
+            }
+        });
+    }
+
+    if non_empty {
+        let msg = format!("{subject} must not be empty");
+
+        checks.push(quote_use! {
+            if value.is_empty() {
+                errors.push(error(value_span, #msg));
+            }
+        });
+    }
+
+    if let Some(max_len) = max_len {
+        let msg = format!(
+            "{subject} must have a length of at most {}",
+            quote::ToTokens::to_token_stream(&max_len)
+        );
+
+        checks.push(quote_use! {
+            if value.len() > #max_len {
+                errors.push(error(value_span, #msg));
+            }
+        });
+    }
+
+    if unique {
+        let msg = format!("{subject} must not contain duplicate elements");
+
+        checks.push(quote_use! {
+            # use core::iter::Iterator;
+
+            if value.iter().enumerate().any(|(i, a)| value[..i].contains(a)) {
+                errors.push(error(value_span, #msg));
+            }
+        });
+    }
+
+    if let Some(pattern) = pattern {
+        let (parse, msg) = match pattern {
+            Pattern::Ident => (
+                quote_use!(syn::parse_str::<syn::Ident>),
+                format!("{subject} must be a valid Rust identifier"),
+            ),
+            Pattern::Path => (
+                quote_use!(syn::parse_str::<syn::Path>),
+                format!("{subject} must be a valid Rust path"),
+            ),
+            Pattern::Type => (
+                quote_use!(syn::parse_str::<syn::Type>),
+                format!("{subject} must be a valid Rust type"),
+            ),
+        };
+
+        checks.push(quote_use! {
+            if #parse(value).is_err() {
+                errors.push(error(value_span, #msg));
+            }
+        });
+    }
+
+    if checks.is_empty() {
+        return checks;
+    }
+
+    // the checks of an `Option<T>` field apply to the inner value, and are skipped for `None`
+    let checks = if option_inner_type(ty) {
+        quote_use! {
+            # use core::option::Option::Some;
+
+            if let Some(value) = &value {
+                #(#checks)*
+            }
+        }
+    } else {
+        quote_use! {
+            let value = &value;
+            #(#checks)*
+        }
+    };
+
+    vec![quote_use! {
+        # use core::option::Option::Some;
+        # use std::string::ToString;
+        # use from_attr::{Error, ErrorKind};
+        # use from_attr::__internal::syn;
+        # use from_attr::__internal::proc_macro2::Span;
+
+        {
+            let error = |span: Span, msg: &str| {
+                let kind = ErrorKind::Validation {
+                    name: Some(ToString::to_string(#name)),
+                };
+
+                Error::new(kind, syn::Error::new(span, msg))
+            };
+
+            #checks
+        }
+    }]
+}
+
+/// Whether the type is written as `Option<T>`.
+fn option_inner_type(ty: &Type) -> bool {
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return false;
+    };
+
+    path.segments
+        .last()
+        .is_some_and(|segment| segment.ident == "Option")
+}
+
+/// Build the value with the values of `fields` and `construct_self`, then run `validate`.
+///
+/// The errors of the relations, the conversions and the validators are all collected,
+/// the value is only built once every given value has been converted.
+fn construct(
+    fields: Option<&GeneratedFields>,
+    construct_self: TokenStream,
+    validate: Option<&Expr>,
+    spans: TokenStream,
+) -> TokenStream {
+    let is_empty = fields
+        .is_none_or(|fields| fields.field_values.is_empty() && fields.dependent_values.is_empty());
+
+    if is_empty && validate.is_none() {
+        return quote_use! {
+            # use core::result::Result::Ok;
+
+            Ok(#construct_self)
+        };
+    }
+
+    let validate = validate.map(|validate| {
+        quote_use! {
+            # use core::option::Option::None;
+            # use core::result::Result::Err;
+            # use from_attr::{Error, ErrorKind};
+
+            if let Err(err) = (#validate)(&value, #spans) {
+                errors.push(Error::new(ErrorKind::Validation { name: None }, err));
+            }
+        }
+    });
+
+    let (relations_check, field_values, dependent_values, condition_checks) = match fields {
+        Some(fields) => (
+            &fields.relations_check[..],
+            &fields.field_values[..],
+            &fields.dependent_values[..],
+            &fields.condition_checks[..],
+        ),
+        None => (&[][..], &[][..], &[][..], &[][..]),
+    };
+
+    // a value is `None` when it cannot be converted, its error is already collected
+    let given_locals = fields
+        .map(|fields| &fields.given_locals[..])
+        .unwrap_or_default();
+
+    let unwrap_given = (!given_locals.is_empty()).then(|| {
+        quote_use! {
+            # use core::option::Option::Some;
+            # use core::result::Result::Err;
+
+            let (#(#given_locals,)*) = match (#(#given_locals,)*) {
+                (#(Some(#given_locals),)*) => (#(#given_locals,)*),
+                _ => {
+                    return Err(errors
+                        .into_error()
+                        .expect("the error of the value is collected"));
+                }
+            };
+        }
+    });
+
+    quote_use! {
+        # use core::option::Option::{Some, None};
+        # use core::result::Result::{Ok, Err};
+        # use from_attr::Diagnostics;
+
+        {
+            #[allow(unused_mut)]
+            let mut errors = Diagnostics::new();
+            #(#relations_check)*
+            #(#field_values)*
+            #unwrap_given
+            #(#dependent_values)*
+            #(#condition_checks)*
+            let value = #construct_self;
+
+            #validate
+
+            errors.finish(value)
+        }
+    }
+}
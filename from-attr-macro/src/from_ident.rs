@@ -3,8 +3,14 @@ use quote::quote;
 use quote_use::quote_use;
 use syn::{Data, DataEnum, DataStruct, DataUnion, DeriveInput, Fields, Variant};
 
+use crate::from_ident_attr::FromIdentAttr;
+
 pub(crate) fn generate(input: DeriveInput) -> syn::Result<TokenStream> {
-    let DeriveInput { ident, data, .. } = input;
+    let DeriveInput {
+        attrs, ident, data, ..
+    } = input;
+
+    let FromIdentAttr { rename_all } = FromIdentAttr::parse_attrs(&attrs)?.unwrap_or_default();
 
     let (brace_token, variants) = match data {
         Data::Enum(DataEnum {
@@ -35,7 +41,7 @@ pub(crate) fn generate(input: DeriveInput) -> syn::Result<TokenStream> {
 
     let mut errors = Vec::new();
     let mut match_arms = Vec::new();
-    let mut idents = String::new();
+    let mut idents = Vec::new();
 
     for Variant { ident, fields, .. } in variants {
         match fields {
@@ -51,15 +57,19 @@ pub(crate) fn generate(input: DeriveInput) -> syn::Result<TokenStream> {
             }
         }
 
-        let ident_string = ident.to_string();
-        let uppercase = ident_string.to_uppercase();
-        let arm = quote! (#uppercase => Self::#ident,);
+        // without `rename_all`, idents are matched case-insensitively
+        let ident_string = match rename_all {
+            Some(rename_all) => rename_all.apply(&ident.to_string()),
+            None => ident.to_string(),
+        };
+        let key = match rename_all {
+            Some(_) => ident_string.clone(),
+            None => ident_string.to_uppercase(),
+        };
+        let arm = quote! (#key => Self::#ident,);
         match_arms.push(arm);
 
-        idents.push('`');
-        idents.push_str(&ident_string);
-        idents.push('`');
-        idents.push_str(", ");
+        idents.push(ident_string);
     }
 
     if let Some(e) = errors.into_iter().reduce(|mut a, b| {
@@ -69,26 +79,41 @@ pub(crate) fn generate(input: DeriveInput) -> syn::Result<TokenStream> {
         return Err(e);
     }
 
-    idents.pop(); // remove whitespace
-    idents.pop(); // remove comma
+    let given = match rename_all {
+        Some(_) => quote_use! {
+            # use std::string::ToString;
+
+            ToString::to_string(&ident)
+        },
+        None => quote_use! {
+            # use std::string::ToString;
+
+            ToString::to_string(&ident).to_uppercase()
+        },
+    };
 
     let expand = quote_use! {
+        # use core::option::Option::None;
         # use std::string::ToString;
-        # use from_attr::{ConvertParsed, PathValue};
+        # use from_attr::{ConvertParsed, Error, ErrorKind, PathValue};
         # use from_attr::__internal::syn::{self, Ident};
+        # use from_attr::__internal::invalid_ident_msg;
 
         impl ConvertParsed for #ident {
             type Type = Ident;
 
-            fn convert(path_value: PathValue<Self::Type>) -> syn::Result<Self> {
+            fn convert(path_value: PathValue<Self::Type>) -> Result<Self, Error> {
                 let ident = path_value.value;
 
-                let this = match ToString::to_string(&ident).to_uppercase().as_str() {
+                let this = match #given.as_str() {
                     #(#match_arms)*
                     _ => {
-                        return Err(syn::Error::new(
-                            ident.span(),
-                            format!("invalid ident: `{}`, valid idents: {}", ident, #idents),
+                        return Err(Error::new(
+                            ErrorKind::Conversion { name: None },
+                            syn::Error::new(
+                                ident.span(),
+                                invalid_ident_msg(&ToString::to_string(&ident), &[#(#idents),*]),
+                            ),
                         ))
                     }
                 };
@@ -0,0 +1,81 @@
+use syn::{meta::ParseNestedMeta, spanned::Spanned, Attribute};
+
+use crate::{case::RenameRule, ATTRIBUTE_IDENT};
+
+#[derive(Default)]
+struct FromIdentAttrOptions {
+    rename_all: Option<RenameRule>,
+}
+
+impl FromIdentAttrOptions {
+    fn parse_meta(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        let meta_path = &meta.path;
+
+        macro_rules! check_duplicate {
+            ($argument:tt) => {
+                if self.$argument.is_some() {
+                    return Err(meta.error(concat!(
+                        "duplicate `",
+                        stringify!($argument),
+                        "` argument"
+                    )));
+                }
+            };
+        }
+
+        if meta_path.is_ident("rename_all") {
+            check_duplicate!(rename_all);
+            self.rename_all = Some(RenameRule::from_lit(&meta.value()?.parse()?)?);
+            return Ok(());
+        }
+
+        Err(meta.error("the argument must be one of: `rename_all`"))
+    }
+
+    fn parse_attr(&mut self, attr: &Attribute) -> syn::Result<()> {
+        attr.parse_nested_meta(|meta| self.parse_meta(meta))
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct FromIdentAttr {
+    pub(crate) rename_all: Option<RenameRule>,
+}
+
+impl FromIdentAttr {
+    pub(crate) fn parse_attrs(attrs: &[Attribute]) -> syn::Result<Option<Self>> {
+        if attrs.is_empty() {
+            return Ok(None);
+        }
+
+        let mut options = FromIdentAttrOptions::default();
+        let mut errors = Vec::new();
+        let mut attr_spans = Vec::new();
+
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident(ATTRIBUTE_IDENT))
+        {
+            attr_spans.push(attr.span());
+
+            if let Err(err) = options.parse_attr(attr) {
+                errors.push(err);
+            }
+        }
+
+        if attr_spans.is_empty() {
+            return Ok(None);
+        }
+
+        if let Some(e) = errors.into_iter().reduce(|mut a, b| {
+            a.combine(b);
+            a
+        }) {
+            return Err(e);
+        }
+
+        let FromIdentAttrOptions { rename_all } = options;
+
+        Ok(Some(Self { rename_all }))
+    }
+}
@@ -1,8 +1,12 @@
+mod case;
 mod field_attr;
 mod flag_or_value;
 mod from_attr;
 mod from_ident;
+mod from_ident_attr;
+mod merge;
 mod struct_attr;
+mod variant_attr;
 
 use proc_macro::TokenStream;
 use syn::{parse_macro_input, DeriveInput};
@@ -18,7 +22,7 @@ pub fn from_attr(input: TokenStream) -> TokenStream {
         .into()
 }
 
-#[proc_macro_derive(FromIdent)]
+#[proc_macro_derive(FromIdent, attributes(attribute))]
 pub fn from_ident(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
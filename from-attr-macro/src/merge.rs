@@ -0,0 +1,30 @@
+use syn::LitStr;
+
+/// How a key given in several attributes is merged, given with `merge = "..."`.
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum MergeStrategy {
+    /// The key is a duplicate.
+    #[default]
+    Error,
+    /// The value of the first attribute is kept.
+    First,
+    /// The value of the last attribute is kept.
+    Last,
+    /// The values are appended, only for collections.
+    Append,
+}
+
+impl MergeStrategy {
+    pub(crate) fn from_lit(lit: &LitStr) -> syn::Result<Self> {
+        match lit.value().as_str() {
+            "error" => Ok(Self::Error),
+            "first" => Ok(Self::First),
+            "last" => Ok(Self::Last),
+            "append" => Ok(Self::Append),
+            _ => Err(syn::Error::new(
+                lit.span(),
+                "the merge strategy must be one of: `error`, `first`, `last`, `append`",
+            )),
+        }
+    }
+}
@@ -1,11 +1,21 @@
 use from_attr_core::Array;
-use syn::{meta::ParseNestedMeta, spanned::Spanned, Attribute, Ident};
+use proc_macro2::Span;
+use syn::{meta::ParseNestedMeta, spanned::Spanned, Attribute, Expr, Ident, LitStr};
 
-use crate::ATTRIBUTE_IDENT;
+use crate::{case::RenameRule, merge::MergeStrategy, ATTRIBUTE_IDENT};
 
 #[derive(Default)]
 struct StructAttrOptions {
     idents: Option<Array<Ident>>,
+    tag: Option<LitStr>,
+    rename_all: Option<RenameRule>,
+    nested: bool,
+    allow_unknown: bool,
+    merge: Option<MergeStrategy>,
+    once: bool,
+    default: bool,
+    validate: Option<Expr>,
+    groups: Vec<ArgGroup>,
 }
 
 impl StructAttrOptions {
@@ -30,7 +40,87 @@ impl StructAttrOptions {
             return Ok(());
         }
 
-        Err(meta.error("the argument must be one of: `idents`"))
+        if meta_path.is_ident("tag") {
+            check_duplicate!(tag);
+            self.tag = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+
+        if meta_path.is_ident("rename_all") {
+            check_duplicate!(rename_all);
+            self.rename_all = Some(RenameRule::from_lit(&meta.value()?.parse()?)?);
+            return Ok(());
+        }
+
+        if meta_path.is_ident("nested") {
+            if self.nested {
+                return Err(meta.error("duplicate `nested` argument"));
+            }
+
+            self.nested = true;
+            return Ok(());
+        }
+
+        if meta_path.is_ident("allow_unknown") {
+            if self.allow_unknown {
+                return Err(meta.error("duplicate `allow_unknown` argument"));
+            }
+
+            self.allow_unknown = true;
+            return Ok(());
+        }
+
+        if meta_path.is_ident("merge") {
+            check_duplicate!(merge);
+            self.merge = Some(MergeStrategy::from_lit(&meta.value()?.parse()?)?);
+            return Ok(());
+        }
+
+        if meta_path.is_ident("once") {
+            if self.once {
+                return Err(meta.error("duplicate `once` argument"));
+            }
+
+            self.once = true;
+            return Ok(());
+        }
+
+        if meta_path.is_ident("default") {
+            if self.default {
+                return Err(meta.error("duplicate `default` argument"));
+            }
+
+            self.default = true;
+            return Ok(());
+        }
+
+        if meta_path.is_ident("validate") {
+            check_duplicate!(validate);
+            self.validate = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+
+        for (name, kind) in [
+            ("one_of", ArgGroupKind::OneOf),
+            ("any_of", ArgGroupKind::AnyOf),
+        ] {
+            if meta_path.is_ident(name) {
+                let array = meta.value()?.parse::<Array<Ident>>()?;
+
+                self.groups.push(ArgGroup {
+                    kind,
+                    span: meta_path.span(),
+                    idents: array.elems.into_iter().collect(),
+                });
+                return Ok(());
+            }
+        }
+
+        Err(meta.error(concat!(
+            "the argument must be one of: ",
+            "`idents`, `tag`, `rename_all`, `nested`, `allow_unknown`, `merge`, `once`, ",
+            "`default`, `validate`, `one_of`, `any_of`"
+        )))
     }
 
     fn parse_attr(&mut self, attr: &Attribute) -> syn::Result<()> {
@@ -40,6 +130,30 @@ impl StructAttrOptions {
 
 pub(crate) struct StructAttr {
     pub(crate) idents: Vec<String>,
+    pub(crate) tag: Option<LitStr>,
+    pub(crate) rename_all: Option<RenameRule>,
+    pub(crate) nested: bool,
+    pub(crate) allow_unknown: bool,
+    pub(crate) merge: MergeStrategy,
+    pub(crate) once: bool,
+    pub(crate) default: bool,
+    pub(crate) validate: Option<Expr>,
+    pub(crate) groups: Vec<ArgGroup>,
+}
+
+/// A group of fields, given with `one_of = [...]` or `any_of = [...]`.
+pub(crate) struct ArgGroup {
+    pub(crate) kind: ArgGroupKind,
+    pub(crate) span: Span,
+    pub(crate) idents: Vec<Ident>,
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum ArgGroupKind {
+    /// Exactly one of the fields must be given.
+    OneOf,
+    /// At least one of the fields must be given.
+    AnyOf,
 }
 
 impl StructAttr {
@@ -74,7 +188,18 @@ impl StructAttr {
             return Err(e);
         }
 
-        let StructAttrOptions { idents } = options;
+        let StructAttrOptions {
+            idents,
+            tag,
+            rename_all,
+            nested,
+            allow_unknown,
+            merge,
+            once,
+            default,
+            validate,
+            groups,
+        } = options;
 
         let idents = idents
             .map(|idents| {
@@ -98,7 +223,18 @@ impl StructAttr {
 
             Err(err)
         } else {
-            Ok(Some(Self { idents }))
+            Ok(Some(Self {
+                idents,
+                tag,
+                rename_all,
+                nested,
+                allow_unknown,
+                merge: merge.unwrap_or_default(),
+                once,
+                default,
+                validate,
+                groups,
+            }))
         }
     }
 }
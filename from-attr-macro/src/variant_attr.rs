@@ -0,0 +1,81 @@
+use syn::{meta::ParseNestedMeta, spanned::Spanned, Attribute, LitStr};
+
+use crate::ATTRIBUTE_IDENT;
+
+#[derive(Default)]
+struct VariantAttrOptions {
+    rename: Option<String>,
+}
+
+impl VariantAttrOptions {
+    fn parse_meta(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        let meta_path = &meta.path;
+
+        macro_rules! check_duplicate {
+            ($argument:tt) => {
+                if self.$argument.is_some() {
+                    return Err(meta.error(concat!(
+                        "duplicate `",
+                        stringify!($argument),
+                        "` argument"
+                    )));
+                }
+            };
+        }
+
+        if meta_path.is_ident("rename") {
+            check_duplicate!(rename);
+            self.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+            return Ok(());
+        }
+
+        Err(meta.error("the argument must be one of: `rename`"))
+    }
+
+    fn parse_attr(&mut self, attr: &Attribute) -> syn::Result<()> {
+        attr.parse_nested_meta(|meta| self.parse_meta(meta))
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct VariantAttr {
+    pub(crate) rename: Option<String>,
+}
+
+impl VariantAttr {
+    pub(crate) fn parse_attrs(attrs: &[Attribute]) -> syn::Result<Option<Self>> {
+        if attrs.is_empty() {
+            return Ok(None);
+        }
+
+        let mut options = VariantAttrOptions::default();
+        let mut errors = Vec::new();
+        let mut attr_spans = Vec::new();
+
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident(ATTRIBUTE_IDENT))
+        {
+            attr_spans.push(attr.span());
+
+            if let Err(err) = options.parse_attr(attr) {
+                errors.push(err);
+            }
+        }
+
+        if attr_spans.is_empty() {
+            return Ok(None);
+        }
+
+        if let Some(e) = errors.into_iter().reduce(|mut a, b| {
+            a.combine(b);
+            a
+        }) {
+            return Err(e);
+        }
+
+        let VariantAttrOptions { rename } = options;
+
+        Ok(Some(Self { rename }))
+    }
+}
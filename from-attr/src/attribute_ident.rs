@@ -5,6 +5,9 @@ pub trait AttributeIdent {
     /// Holds the idents.
     const IDENTS: &'static [&'static str];
 
+    /// Whether the attribute can be given at most once, set with `#[attribute(once)]`.
+    const ONCE: bool = false;
+
     /// Check if path exists in the idents.
     fn is_ident(path: &Path) -> bool {
         Self::IDENTS.iter().any(|ident| path.is_ident(ident))
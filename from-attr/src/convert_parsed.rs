@@ -1,6 +1,12 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    hash::Hash,
+};
+
 use from_attr_core::Array;
 use proc_macro2::{Group, Literal, Punct, TokenTree};
 use syn::{
+    meta::ParseNestedMeta,
     parse_quote,
     token::{
         Abstract, And, AndAnd, AndEq, As, Async, At, Auto, Await, Become, Break, Caret, CaretEq,
@@ -26,7 +32,7 @@ use syn::{
     TypeTraitObject, TypeTuple, UnOp, Variant, Visibility, WhereClause, WherePredicate,
 };
 
-use crate::PathValue;
+use crate::{Diagnostics, Error, ErrorKind, PathValue};
 
 /// Helper trait to convert syn types implementing [`Parse`](syn::parse::Parse) like
 /// [`LitStr`](struct@syn::LitStr) to rust types like [`String`]
@@ -34,13 +40,17 @@ use crate::PathValue;
 /// You probably don't need to implement this trait, as most syn types like
 /// [`LitStr`](struct@syn::LitStr) and [`Type`] or that have a direct equivalent in
 /// those like [`String`], [`char`] or [`f32`] are already implemented. A
-/// special treatment have [`Vec`] which are parsed with the syntax `[a, b, c]`.
+/// special treatment have [`Vec`] and sets which are parsed with the syntax `[a, b, c]`.
 pub trait ConvertParsed: Sized {
     /// The type this can be converted from
     type Type;
 
+    /// Whether [`append`](ConvertParsed::append) appends the values, checked at compile time for
+    /// the fields with `merge = "append"`.
+    const APPENDABLE: bool = false;
+
     /// Convert the [`PathValue<Self::Type>`](crate::PathValue) to self.
-    fn convert(path_value: PathValue<Self::Type>) -> syn::Result<Self>;
+    fn convert(path_value: PathValue<Self::Type>) -> Result<Self, Error>;
 
     /// Returns the value when this type is not specified.
     fn default() -> Option<Self> {
@@ -51,6 +61,17 @@ pub trait ConvertParsed: Sized {
     fn flag() -> Option<Self::Type> {
         None
     }
+
+    /// Parse the value when this type is specified as list, like `b(a = 1)`.
+    fn list(_meta: &ParseNestedMeta) -> Option<Result<Self::Type, Error>> {
+        None
+    }
+
+    /// Append `other` to `value` when the key is given in several attributes with
+    /// `merge = "append"`, returns `None` when this type cannot be appended.
+    fn append(_value: Self::Type, _other: Self::Type) -> Option<Self::Type> {
+        None
+    }
 }
 
 impl<T> ConvertParsed for Option<T>
@@ -59,7 +80,9 @@ where
 {
     type Type = T::Type;
 
-    fn convert(path_value: PathValue<Self::Type>) -> syn::Result<Self> {
+    const APPENDABLE: bool = T::APPENDABLE;
+
+    fn convert(path_value: PathValue<Self::Type>) -> Result<Self, Error> {
         Ok(Some(T::convert(path_value)?))
     }
 
@@ -70,6 +93,14 @@ where
     fn flag() -> Option<Self::Type> {
         T::flag()
     }
+
+    fn list(meta: &ParseNestedMeta) -> Option<Result<Self::Type, Error>> {
+        T::list(meta)
+    }
+
+    fn append(value: Self::Type, other: Self::Type) -> Option<Self::Type> {
+        T::append(value, other)
+    }
 }
 
 impl<T> ConvertParsed for Vec<T>
@@ -78,11 +109,13 @@ where
 {
     type Type = Array<T::Type>;
 
-    fn convert(path_value: PathValue<Self::Type>) -> syn::Result<Self> {
+    const APPENDABLE: bool = true;
+
+    fn convert(path_value: PathValue<Self::Type>) -> Result<Self, Error> {
         let PathValue { path, value } = path_value;
 
         let mut elems = Vec::new();
-        let mut errors = Vec::new();
+        let mut errors = Diagnostics::new();
 
         value
             .elems
@@ -92,24 +125,65 @@ where
                 Err(e) => errors.push(e),
             });
 
-        match errors.into_iter().reduce(|mut a, b| {
-            a.combine(b);
-            a
-        }) {
-            Some(e) => Err(e),
-            None => Ok(elems),
-        }
+        errors.finish(elems)
     }
 
     fn default() -> Option<Self> {
         Some(Vec::new())
     }
+
+    fn append(mut value: Self::Type, other: Self::Type) -> Option<Self::Type> {
+        value.elems.extend(other.elems);
+        Some(value)
+    }
+}
+
+impl<T> ConvertParsed for BTreeSet<T>
+where
+    T: ConvertParsed + Ord,
+{
+    type Type = Array<T::Type>;
+
+    const APPENDABLE: bool = true;
+
+    fn convert(path_value: PathValue<Self::Type>) -> Result<Self, Error> {
+        Ok(Vec::<T>::convert(path_value)?.into_iter().collect())
+    }
+
+    fn default() -> Option<Self> {
+        Some(BTreeSet::new())
+    }
+
+    fn append(value: Self::Type, other: Self::Type) -> Option<Self::Type> {
+        <Vec<T> as ConvertParsed>::append(value, other)
+    }
+}
+
+impl<T> ConvertParsed for HashSet<T>
+where
+    T: ConvertParsed + Hash + std::cmp::Eq,
+{
+    type Type = Array<T::Type>;
+
+    const APPENDABLE: bool = true;
+
+    fn convert(path_value: PathValue<Self::Type>) -> Result<Self, Error> {
+        Ok(Vec::<T>::convert(path_value)?.into_iter().collect())
+    }
+
+    fn default() -> Option<Self> {
+        Some(HashSet::new())
+    }
+
+    fn append(value: Self::Type, other: Self::Type) -> Option<Self::Type> {
+        <Vec<T> as ConvertParsed>::append(value, other)
+    }
 }
 
 impl ConvertParsed for bool {
     type Type = LitBool;
 
-    fn convert(path_value: PathValue<Self::Type>) -> syn::Result<Self> {
+    fn convert(path_value: PathValue<Self::Type>) -> Result<Self, Error> {
         Ok(path_value.value.value)
     }
 
@@ -127,7 +201,7 @@ macro_rules! get_value {
         impl ConvertParsed for $to {
             type Type = $from;
 
-            fn convert(path_value: PathValue<$from>) -> syn::Result<$to> {
+            fn convert(path_value: PathValue<$from>) -> Result<$to, Error> {
                 Ok($get(&path_value.value))
             }
         }
@@ -143,8 +217,9 @@ macro_rules! parse_value {
             impl ConvertParsed for $to {
                 type Type = $from;
 
-                fn convert(path_value: PathValue<$from>) -> syn::Result<$to> {
+                fn convert(path_value: PathValue<$from>) -> Result<$to, Error> {
                     $parse(&path_value.value)
+                        .map_err(|err| Error::new(ErrorKind::Conversion { name: None }, err))
                 }
             }
         )*
@@ -160,7 +235,7 @@ macro_rules! convert_parsed {
             impl ConvertParsed for $type {
                 type Type = $type;
 
-                fn convert(path_value: PathValue<Self>) -> syn::Result<Self> {
+                fn convert(path_value: PathValue<Self>) -> Result<Self, Error> {
                     Ok(path_value.value)
                 }
             }
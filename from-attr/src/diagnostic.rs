@@ -0,0 +1,139 @@
+use std::fmt::Display;
+
+use proc_macro2::{Span, TokenStream};
+
+use crate::{Error, ErrorKind};
+
+/// An error with a primary span, notes pointing at other spans and `help:` lines.
+///
+/// Converted into [`syn::Error`] to be rendered as `compile_error!` on stable, the help lines are
+/// appended to the primary message and each note is an error of its own, like
+/// "note: first defined here", on its span.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    span: Span,
+    message: String,
+    notes: Vec<(Span, String)>,
+    helps: Vec<String>,
+}
+
+impl Diagnostic {
+    /// Create an error on `span`.
+    pub fn new(span: Span, message: impl Display) -> Self {
+        Self {
+            span,
+            message: message.to_string(),
+            notes: Vec::new(),
+            helps: Vec::new(),
+        }
+    }
+
+    /// Add a note on another span, like where a duplicate key was first defined.
+    pub fn note(mut self, span: Span, message: impl Display) -> Self {
+        self.notes.push((span, message.to_string()));
+        self
+    }
+
+    /// Add a `help:` line to the message.
+    pub fn help(mut self, message: impl Display) -> Self {
+        self.helps.push(message.to_string());
+        self
+    }
+
+    /// The primary span.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Render as `compile_error!` invocations.
+    pub fn to_compile_error(&self) -> TokenStream {
+        syn::Error::from(self.clone()).to_compile_error()
+    }
+}
+
+impl From<Diagnostic> for syn::Error {
+    fn from(diagnostic: Diagnostic) -> Self {
+        let Diagnostic {
+            span,
+            mut message,
+            notes,
+            helps,
+        } = diagnostic;
+
+        for help in helps {
+            message.push_str("\nhelp: ");
+            message.push_str(&help);
+        }
+
+        let mut err = syn::Error::new(span, message);
+
+        for (span, note) in notes {
+            err.combine(syn::Error::new(span, format!("note: {}", note)));
+        }
+
+        err
+    }
+}
+
+/// Collects errors to report all of them at once.
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+    error: Option<Error>,
+}
+
+impl Diagnostics {
+    /// Create an empty collection.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The same error of `kind` on each of the `spans`, generally the spans of the attributes.
+    pub fn spanned(spans: &[Span], kind: ErrorKind, message: impl Display) -> Self {
+        spans
+            .iter()
+            .map(|span| Error::new(kind.clone(), syn::Error::new(*span, &message)))
+            .collect()
+    }
+
+    /// Add an error.
+    pub fn push(&mut self, error: impl Into<Error>) {
+        let error = error.into();
+
+        match &mut self.error {
+            Some(err) => err.combine(error),
+            None => self.error = Some(error),
+        }
+    }
+
+    /// Whether no error is collected.
+    pub fn is_empty(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// The collected errors combined, if any.
+    pub fn into_error(self) -> Option<Error> {
+        self.error
+    }
+
+    /// `value` if no error is collected, otherwise the collected errors combined.
+    pub fn finish<T>(self, value: T) -> Result<T, Error> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(value),
+        }
+    }
+}
+
+impl<E: Into<Error>> Extend<E> for Diagnostics {
+    fn extend<I: IntoIterator<Item = E>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|error| self.push(error));
+    }
+}
+
+impl<E: Into<Error>> FromIterator<E> for Diagnostics {
+    fn from_iter<I: IntoIterator<Item = E>>(iter: I) -> Self {
+        let mut diagnostics = Self::new();
+        diagnostics.extend(iter);
+        diagnostics
+    }
+}
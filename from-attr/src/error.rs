@@ -0,0 +1,179 @@
+use std::fmt::{self, Display};
+
+use proc_macro2::{Span, TokenStream};
+
+use crate::Diagnostic;
+
+/// The kind of an [`Error`], to tell the errors apart without looking at the messages.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The tokens cannot be parsed, like a literal of the wrong type.
+    Syntax,
+    /// A named argument that is not accepted.
+    UnknownField {
+        /// The name of the argument.
+        name: String,
+    },
+    /// A named argument that is given again.
+    DuplicateField {
+        /// The name of the argument.
+        name: String,
+        /// Where the argument is first given.
+        first: Span,
+    },
+    /// A required argument that is not given.
+    MissingField {
+        /// The name of the argument.
+        name: String,
+    },
+    /// Two mutually exclusive arguments that are given together.
+    Conflict {
+        /// The name of the argument the error points at.
+        name: String,
+        /// The name of the other argument.
+        other: String,
+        /// Where the other argument is given.
+        other_span: Span,
+    },
+    /// An argument that is given without the argument it requires.
+    Requires {
+        /// The name of the argument.
+        name: String,
+        /// The name of the required argument.
+        required: String,
+    },
+    /// A group of arguments of which none or too many are given, also used for the variants of enums.
+    Group {
+        /// The names of the arguments in the group.
+        names: Vec<String>,
+    },
+    /// A value that cannot be converted.
+    Conversion {
+        /// The name of the argument the value is given to, if known.
+        name: Option<String>,
+    },
+    /// A value that is rejected by a validator.
+    Validation {
+        /// The name of the field, `None` when the whole value is validated.
+        name: Option<String>,
+    },
+    /// An attribute that is given again when it can be given at most once.
+    DuplicateAttribute {
+        /// Where the attribute is first given.
+        first: Span,
+    },
+    /// A required attribute that is not given.
+    MissingAttribute,
+    /// Any other error.
+    Other,
+}
+
+/// The error of parsing attributes, one or more [`syn::Error`]s each with its [`ErrorKind`].
+///
+/// Converts into [`syn::Error`], and iterates its messages as [`syn::Error`]s like [`syn::Error`]
+/// does. A [`syn::Error`] converts into an error of kind [`ErrorKind::Syntax`].
+#[derive(Debug, Clone)]
+pub struct Error {
+    errors: Vec<(ErrorKind, syn::Error)>,
+}
+
+impl Error {
+    /// Create an error of `kind`.
+    pub fn new(kind: ErrorKind, error: impl Into<syn::Error>) -> Self {
+        Self {
+            errors: vec![(kind, error.into())],
+        }
+    }
+
+    /// The kind of the first error.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.errors[0].0
+    }
+
+    /// The kinds of all the errors.
+    pub fn kinds(&self) -> impl Iterator<Item = &ErrorKind> {
+        self.errors.iter().map(|(kind, _)| kind)
+    }
+
+    /// All the errors with their kinds.
+    pub fn iter(&self) -> impl Iterator<Item = (&ErrorKind, &syn::Error)> {
+        self.errors.iter().map(|(kind, error)| (kind, error))
+    }
+
+    /// The span of the first error.
+    pub fn span(&self) -> Span {
+        self.errors[0].1.span()
+    }
+
+    /// Add the errors of `another`.
+    pub fn combine(&mut self, another: Error) {
+        self.errors.extend(another.errors);
+    }
+
+    /// Set the name of the argument of the conversion and validation errors that have none.
+    pub fn with_name(mut self, name: &str) -> Self {
+        for (kind, _) in &mut self.errors {
+            if let ErrorKind::Conversion {
+                name: kind_name @ None,
+            }
+            | ErrorKind::Validation {
+                name: kind_name @ None,
+            } = kind
+            {
+                *kind_name = Some(name.to_string());
+            }
+        }
+
+        self
+    }
+
+    /// Render as `compile_error!` invocations.
+    pub fn to_compile_error(&self) -> TokenStream {
+        self.errors
+            .iter()
+            .map(|(_, error)| error.to_compile_error())
+            .collect()
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.errors[0].1, f)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<syn::Error> for Error {
+    fn from(error: syn::Error) -> Self {
+        Self::new(ErrorKind::Syntax, error)
+    }
+}
+
+impl From<Diagnostic> for Error {
+    fn from(diagnostic: Diagnostic) -> Self {
+        Self::new(ErrorKind::Other, diagnostic)
+    }
+}
+
+impl From<Error> for syn::Error {
+    fn from(error: Error) -> Self {
+        let mut errors = error.errors.into_iter().map(|(_, error)| error);
+        let mut first = errors.next().expect("an error is not empty");
+        errors.for_each(|error| first.combine(error));
+        first
+    }
+}
+
+impl IntoIterator for Error {
+    type Item = syn::Error;
+    type IntoIter = std::vec::IntoIter<syn::Error>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        syn::Error::from(self)
+            .into_iter()
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
@@ -1,6 +1,7 @@
 use proc_macro2::Span;
+use syn::meta::ParseNestedMeta;
 
-use crate::{flag_or_type::FlagOrType, ConvertParsed, PathValue};
+use crate::{flag_or_type::FlagOrType, ConvertParsed, Error, PathValue};
 
 /// Represents the 3 cases parsed from the [`meta`](syn::meta::ParseNestedMeta).
 #[derive(Clone, Copy, Debug, Default)]
@@ -28,7 +29,7 @@ where
 {
     type Type = FlagOrType<T::Type>;
 
-    fn convert(path_value: PathValue<Self::Type>) -> syn::Result<Self> {
+    fn convert(path_value: PathValue<Self::Type>) -> Result<Self, Error> {
         let PathValue { path, value } = path_value;
 
         match value {
@@ -47,4 +48,8 @@ where
     fn flag() -> Option<Self::Type> {
         Some(FlagOrType::Flag)
     }
+
+    fn list(meta: &ParseNestedMeta) -> Option<Result<Self::Type, Error>> {
+        T::list(meta).map(|value| value.map(FlagOrType::Type))
+    }
 }
@@ -1,7 +1,8 @@
 use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
 use syn::{spanned::Spanned, Attribute, MetaList};
 
-use crate::{AttributeIdent, AttrsValue, ParseMeta};
+use crate::{AttributeIdent, AttrsValue, Diagnostic, Diagnostics, Error, ErrorKind, ParseMeta};
 
 /// Used for conversion from [`Attribute`]s, [`MetaList`], [`TokenStream`] to values.
 ///
@@ -13,10 +14,10 @@ pub trait FromAttr: Sized {
     type Parser: ParseMeta + Default;
 
     /// Convert from [`Parser`](type@crate::FromAttr::Parser) to values.
-    fn from_parser(parser: Self::Parser, spans: &[Span]) -> syn::Result<Self>;
+    fn from_parser(parser: Self::Parser, spans: &[Span]) -> Result<Self, Error>;
 
     /// Convert from [`MetaList`] to values.
-    fn from_meta_list(list: &MetaList) -> syn::Result<Option<Self>>
+    fn from_meta_list(list: &MetaList) -> Result<Option<Self>, Error>
     where
         Self: AttributeIdent,
     {
@@ -35,7 +36,7 @@ pub trait FromAttr: Sized {
     /// *Does not* remove [`Attribute`]s and is generally used to parse attributes of derive macros.
     fn from_attributes(
         attrs: &[Attribute],
-    ) -> Result<Option<AttrsValue<&Attribute, Self>>, AttrsValue<&Attribute, syn::Error>>
+    ) -> Result<Option<AttrsValue<&Attribute, Self>>, AttrsValue<&Attribute, Error>>
     where
         Self: AttributeIdent,
     {
@@ -46,28 +47,27 @@ pub trait FromAttr: Sized {
         let mut matched_attr_spans = Vec::new();
         let mut matched_attrs = Vec::new();
         let mut parser_default = Self::Parser::default();
-        let mut errors = Vec::new();
+        let mut errors = Diagnostics::new();
 
         attrs
             .iter()
             .filter(|attr| <Self as AttributeIdent>::is_ident(attr.path()))
             .for_each(|attr| {
-                matched_attr_spans.push(attr.span());
-                matched_attrs.push(attr);
-
-                if let Err(err) = parser_default.parse_attr(attr) {
+                if let Some(err) = once_error::<Self>(&matched_attr_spans, attr) {
+                    errors.push(err);
+                } else if let Err(err) = parser_default.parse_attr(attr) {
                     errors.push(err);
                 };
+
+                matched_attr_spans.push(attr.span());
+                matched_attrs.push(attr);
             });
 
         if matched_attrs.is_empty() {
             return Ok(None);
         }
 
-        if let Some(e) = errors.into_iter().reduce(|mut a, b| {
-            a.combine(b);
-            a
-        }) {
+        if let Some(e) = errors.into_error() {
             return Err(AttrsValue {
                 attrs: matched_attrs,
                 value: e,
@@ -91,7 +91,7 @@ pub trait FromAttr: Sized {
     /// *Does* remove [`Attribute`]s and is generally used to parse attributes of attribute macros.
     fn remove_attributes(
         attrs: &mut Vec<Attribute>,
-    ) -> Result<Option<AttrsValue<Attribute, Self>>, AttrsValue<Attribute, syn::Error>>
+    ) -> Result<Option<AttrsValue<Attribute, Self>>, AttrsValue<Attribute, Error>>
     where
         Self: AttributeIdent,
     {
@@ -102,7 +102,7 @@ pub trait FromAttr: Sized {
         let mut matched_attr_spans = Vec::new();
         let mut matched_attrs = Vec::new();
         let mut parser_default = Self::Parser::default();
-        let mut errors = Vec::new();
+        let mut errors = Diagnostics::new();
 
         let mut i = 0;
 
@@ -112,7 +112,9 @@ pub trait FromAttr: Sized {
             } else {
                 let attr = attrs.remove(i);
 
-                if let Err(err) = parser_default.parse_attr(&attr) {
+                if let Some(err) = once_error::<Self>(&matched_attr_spans, &attr) {
+                    errors.push(err);
+                } else if let Err(err) = parser_default.parse_attr(&attr) {
                     errors.push(err);
                 };
 
@@ -125,10 +127,7 @@ pub trait FromAttr: Sized {
             return Ok(None);
         }
 
-        if let Some(e) = errors.into_iter().reduce(|mut a, b| {
-            a.combine(b);
-            a
-        }) {
+        if let Some(e) = errors.into_error() {
             return Err(AttrsValue {
                 attrs: matched_attrs,
                 value: e,
@@ -147,13 +146,190 @@ pub trait FromAttr: Sized {
         }
     }
 
+    /// Convert from [`Attribute`]s to values, or the default values of the fields if there is no
+    /// matching [`Attribute`], as if an empty one was given.
+    ///
+    /// *Does not* remove [`Attribute`]s, if a field must be given, the missing attribute is an
+    /// error.
+    fn from_attributes_or_default(
+        attrs: &[Attribute],
+    ) -> Result<Self, AttrsValue<&Attribute, Error>>
+    where
+        Self: AttributeIdent,
+    {
+        if let Some(attrs_value) = Self::from_attributes(attrs)? {
+            return Ok(attrs_value.value);
+        }
+
+        Self::from_parser(Self::Parser::default(), &[Span::call_site()]).map_err(|err| {
+            let is_missing = err.kinds().any(|kind| {
+                matches!(
+                    kind,
+                    ErrorKind::MissingField { .. } | ErrorKind::Group { .. }
+                )
+            });
+
+            AttrsValue {
+                attrs: Vec::new(),
+                value: if is_missing {
+                    missing_attribute_error::<Self>(Span::call_site())
+                } else {
+                    err
+                },
+            }
+        })
+    }
+
+    /// Convert from [`Attribute`]s to values, the [`Attribute`] must be given.
+    ///
+    /// *Does not* remove [`Attribute`]s, if there is no matching [`Attribute`], the error is
+    /// spanned on `item_span`, generally the span of the item the attributes are on.
+    fn require_from_attributes(
+        attrs: &[Attribute],
+        item_span: Span,
+    ) -> Result<AttrsValue<&Attribute, Self>, AttrsValue<&Attribute, Error>>
+    where
+        Self: AttributeIdent,
+    {
+        Self::from_attributes(attrs)?.ok_or_else(|| AttrsValue {
+            attrs: Vec::new(),
+            value: missing_attribute_error::<Self>(item_span),
+        })
+    }
+
+    /// Convert each of the [`Attribute`]s to its own value.
+    ///
+    /// *Does not* remove [`Attribute`]s, the values are in the order of the attributes, and the
+    /// errors of all attributes are combined.
+    fn from_attributes_each(
+        attrs: &[Attribute],
+    ) -> Result<Vec<AttrsValue<&Attribute, Self>>, AttrsValue<&Attribute, Error>>
+    where
+        Self: AttributeIdent,
+    {
+        let matched_attrs = attrs
+            .iter()
+            .filter(|attr| <Self as AttributeIdent>::is_ident(attr.path()))
+            .collect::<Vec<_>>();
+
+        let mut values = Vec::new();
+        let mut errors = Diagnostics::new();
+
+        for attr in &matched_attrs {
+            match from_single_attribute::<Self>(attr) {
+                Ok(value) => values.push(AttrsValue {
+                    attrs: vec![*attr],
+                    value,
+                }),
+                Err(err) => errors.push(err),
+            }
+        }
+
+        match errors.into_error() {
+            Some(e) => Err(AttrsValue {
+                attrs: matched_attrs,
+                value: e,
+            }),
+            None => Ok(values),
+        }
+    }
+
+    /// Convert each of the [`Attribute`]s to its own value.
+    ///
+    /// *Does* remove [`Attribute`]s, the values are in the order of the attributes, and the
+    /// errors of all attributes are combined.
+    fn remove_attributes_each(
+        attrs: &mut Vec<Attribute>,
+    ) -> Result<Vec<AttrsValue<Attribute, Self>>, AttrsValue<Attribute, Error>>
+    where
+        Self: AttributeIdent,
+    {
+        let mut matched_attrs = Vec::new();
+        let mut i = 0;
+
+        while i < attrs.len() {
+            if !<Self as AttributeIdent>::is_ident(attrs[i].path()) {
+                i += 1;
+            } else {
+                matched_attrs.push(attrs.remove(i));
+            }
+        }
+
+        let mut values = Vec::new();
+        let mut errors = Diagnostics::new();
+
+        for attr in &matched_attrs {
+            match from_single_attribute::<Self>(attr) {
+                Ok(value) => values.push(value),
+                Err(err) => errors.push(err),
+            }
+        }
+
+        if let Some(e) = errors.into_error() {
+            return Err(AttrsValue {
+                attrs: matched_attrs,
+                value: e,
+            });
+        }
+
+        Ok(matched_attrs
+            .into_iter()
+            .zip(values)
+            .map(|(attr, value)| AttrsValue {
+                attrs: vec![attr],
+                value,
+            })
+            .collect())
+    }
+
     /// Convert from [`TokenStream`] to values.
     ///
     /// Generally used for parsing [`TokenStream`] for attribute macros.
-    fn from_tokens(tokens: TokenStream) -> syn::Result<Self> {
+    fn from_tokens(tokens: TokenStream) -> Result<Self, Error> {
         let mut parser_default = Self::Parser::default();
         let spans = vec![tokens.span()];
         parser_default.parse_tokens(tokens)?;
         Self::from_parser(parser_default, &spans)
     }
 }
+
+/// Convert one [`Attribute`] with its own parser, its span is used by missing-field errors.
+fn from_single_attribute<T: FromAttr>(attr: &Attribute) -> Result<T, Error> {
+    let mut parser_default = T::Parser::default();
+    parser_default.parse_attr(attr)?;
+    T::from_parser(parser_default, &[attr.span()])
+}
+
+/// The error for an attribute given again when it can be given at most once, with a note pointing
+/// at the first one.
+fn once_error<T: AttributeIdent>(matched_attr_spans: &[Span], attr: &Attribute) -> Option<Error> {
+    if !T::ONCE {
+        return None;
+    }
+
+    let first = matched_attr_spans.first()?;
+    let name = attr.path().to_token_stream().to_string().replace(' ', "");
+
+    let diagnostic = Diagnostic::new(attr.span(), format!("duplicate `{}` attribute", name))
+        .help(format!("merge the arguments into one `{}` attribute", name))
+        .note(*first, format!("the first `{}` attribute is here", name));
+
+    Some(Error::new(
+        ErrorKind::DuplicateAttribute { first: *first },
+        diagnostic,
+    ))
+}
+
+/// The error for a missing attribute, like "missing `#[test(...)]` attribute".
+fn missing_attribute_error<T: AttributeIdent>(span: Span) -> Error {
+    let idents = T::IDENTS
+        .iter()
+        .map(|ident| format!("`#[{}(...)]`", ident))
+        .collect::<Vec<_>>()
+        .join(" or ");
+
+    Error::new(
+        ErrorKind::MissingAttribute,
+        syn::Error::new(span, format!("missing {} attribute", idents)),
+    )
+}
@@ -0,0 +1,37 @@
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{Meta, Path};
+
+use crate::Map;
+
+/// Used for collecting the unknown arguments into a field marked with `#[attribute(rest)]`.
+///
+/// Implemented for [`Vec<syn::Meta>`] and [`Map<syn::Path, TokenStream>`](crate::Map), where the
+/// value is the tokens after `=`, the tokens inside the parentheses, or nothing for a bare path.
+pub trait FromRest: Sized {
+    /// Convert from the unknown arguments, in the order they were given.
+    fn from_rest(metas: Vec<Meta>) -> syn::Result<Self>;
+}
+
+impl FromRest for Vec<Meta> {
+    fn from_rest(metas: Vec<Meta>) -> syn::Result<Self> {
+        Ok(metas)
+    }
+}
+
+impl FromRest for Map<Path, TokenStream> {
+    fn from_rest(metas: Vec<Meta>) -> syn::Result<Self> {
+        let pairs = metas
+            .into_iter()
+            .map(|meta| match meta {
+                Meta::Path(path) => (path, TokenStream::new()),
+                Meta::List(list) => (list.path, list.tokens),
+                Meta::NameValue(name_value) => {
+                    (name_value.path, name_value.value.into_token_stream())
+                }
+            })
+            .collect();
+
+        Ok(Map(pairs))
+    }
+}
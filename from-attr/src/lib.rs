@@ -4,13 +4,21 @@
 mod attribute_ident;
 mod attrs_value;
 mod convert_parsed;
+mod diagnostic;
+mod error;
 mod flag_or_type;
 mod flag_or_value;
 mod from_attr;
+mod from_rest;
 mod macros;
 mod map;
+mod multiple;
+mod nested;
 mod parse_meta;
 mod path_value;
+mod spanned;
+mod spanned_type;
+mod suggestion;
 
 #[doc(inline)]
 #[doc = include_str!("./docs/from_attr.md")]
@@ -20,13 +28,32 @@ pub use from_attr_macro::FromAttr;
 pub use from_attr_macro::FromIdent;
 
 pub use self::{
-    attribute_ident::AttributeIdent, attrs_value::AttrsValue, convert_parsed::ConvertParsed,
-    flag_or_value::FlagOrValue, from_attr::FromAttr, map::Map, parse_meta::ParseMeta,
+    attribute_ident::AttributeIdent,
+    attrs_value::AttrsValue,
+    convert_parsed::ConvertParsed,
+    diagnostic::{Diagnostic, Diagnostics},
+    error::{Error, ErrorKind},
+    flag_or_value::FlagOrValue,
+    from_attr::FromAttr,
+    from_rest::FromRest,
+    map::Map,
+    multiple::Multiple,
+    nested::Nested,
+    parse_meta::ParseMeta,
     path_value::PathValue,
+    spanned::Spanned,
 };
 
 #[doc(hidden)]
 pub mod __internal {
     pub use proc_macro2;
     pub use syn;
+
+    pub use crate::parse_meta::{
+        concat_fields, contains_field, is_named_argument, parse_meta_speculatively,
+        parse_unknown_meta, skip_token_trees, unknown_field_error, unknown_field_msg,
+    };
+    pub use crate::suggestion::{
+        did_you_mean, invalid_ident_msg, join_names, unknown_argument_msg, unknown_ident_msg,
+    };
 }
@@ -2,7 +2,8 @@
 ///
 /// Requires that the type has implemented the [`FromAttr`](trait@crate::FromAttr) and [`AttributeIdent`](crate::AttributeIdent) traits.
 ///
-/// Generally used for parsing nested attributes.
+/// Generally used for parsing nested attributes. With the derive macro, `#[attribute(nested)]`
+/// can be used instead, which also accepts the keyless form `b(a = 10)`.
 ///
 /// # Example
 ///
@@ -43,21 +44,19 @@ macro_rules! convert_parsed_from_meta_list {
 
             fn convert(
                 path_value: $crate::PathValue<Self::Type>,
-            ) -> $crate::__internal::syn::Result<Self> {
+            ) -> ::core::result::Result<Self, $crate::Error> {
                 match <Self as $crate::FromAttr>::from_meta_list(&path_value.value)? {
                     Some(a) => Ok(a),
-                    None => {
-                        let idents = <Self as $crate::AttributeIdent>::IDENTS
-                            .iter()
-                            .map(|ident| format!("`{}`", *ident))
-                            .collect::<::std::vec::Vec<_>>()
-                            .join(", ");
-
-                        Err($crate::__internal::syn::Error::new(
+                    None => Err($crate::Error::new(
+                        $crate::ErrorKind::Conversion { name: None },
+                        $crate::__internal::syn::Error::new(
                             path_value.path,
-                            format!("expected idents: {}", idents),
-                        ))
-                    }
+                            $crate::__internal::unknown_ident_msg(
+                                &path_value.value.path,
+                                <Self as $crate::AttributeIdent>::IDENTS,
+                            ),
+                        ),
+                    )),
                 }
             }
         }
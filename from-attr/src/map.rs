@@ -1,6 +1,6 @@
 use from_attr_core::Pair;
 
-use crate::{ConvertParsed, PathValue};
+use crate::{ConvertParsed, Diagnostics, Error, PathValue};
 
 /// Represents a map parsed from the [`meta`](syn::meta::ParseNestedMeta).
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Default, Clone)]
@@ -13,11 +13,13 @@ where
 {
     type Type = from_attr_core::Map<K::Type, V::Type>;
 
-    fn convert(path_value: PathValue<Self::Type>) -> syn::Result<Self> {
+    const APPENDABLE: bool = true;
+
+    fn convert(path_value: PathValue<Self::Type>) -> Result<Self, Error> {
         let PathValue { path, value } = path_value;
 
         let mut pairs = Vec::new();
-        let mut errors = Vec::new();
+        let mut errors = Diagnostics::new();
 
         value.pairs.into_iter().for_each(|Pair { key, value, .. }| {
             match (
@@ -30,16 +32,15 @@ where
             }
         });
 
-        match errors.into_iter().reduce(|mut a, b| {
-            a.combine(b);
-            a
-        }) {
-            Some(e) => Err(e),
-            None => Ok(Map(pairs)),
-        }
+        errors.finish(Map(pairs))
     }
 
     fn default() -> Option<Self> {
         Some(Map(Vec::new()))
     }
+
+    fn append(mut value: Self::Type, other: Self::Type) -> Option<Self::Type> {
+        value.pairs.extend(other.pairs);
+        Some(value)
+    }
 }
@@ -0,0 +1,152 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    hash::Hash,
+};
+
+use from_attr_core::Pair;
+use proc_macro2::Span;
+use syn::{
+    braced, bracketed,
+    parse::{Parse, ParseStream},
+    token, Token,
+};
+
+use crate::{ConvertParsed, Diagnostics, Error, Map, PathValue};
+
+/// Used for collections whose elements can be given by repeating the key, with
+/// `#[attribute(multiple)]`.
+///
+/// Each occurrence is either one element or several elements in the syntax of the collection,
+/// like `a = 1, a = [2, 3]` for [`Vec`] and sets, or `a = "k": 1, a = { "l": 2 }` for [`Map`].
+pub trait Multiple: Sized {
+    /// The type one element is parsed into.
+    type Item;
+
+    /// Parse one occurrence, returns the elements with their spans.
+    fn parse_items(input: ParseStream) -> syn::Result<Vec<(Self::Item, Span)>>;
+
+    /// Convert from the elements of every occurrence, in the order they were given.
+    fn from_items(items: Vec<PathValue<Self::Item>>) -> Result<Self, Error>;
+}
+
+impl<T> Multiple for Vec<T>
+where
+    T: ConvertParsed,
+    T::Type: Parse,
+{
+    type Item = T::Type;
+
+    fn parse_items(input: ParseStream) -> syn::Result<Vec<(Self::Item, Span)>> {
+        if input.peek(token::Bracket) {
+            let content;
+            bracketed!(content in input);
+            parse_elements(&content)
+        } else {
+            let span = input.span();
+            Ok(vec![(input.parse()?, span)])
+        }
+    }
+
+    fn from_items(items: Vec<PathValue<Self::Item>>) -> Result<Self, Error> {
+        collect_results(items.into_iter().map(T::convert))
+    }
+}
+
+impl<T> Multiple for BTreeSet<T>
+where
+    T: ConvertParsed + Ord,
+    T::Type: Parse,
+{
+    type Item = T::Type;
+
+    fn parse_items(input: ParseStream) -> syn::Result<Vec<(Self::Item, Span)>> {
+        Vec::<T>::parse_items(input)
+    }
+
+    fn from_items(items: Vec<PathValue<Self::Item>>) -> Result<Self, Error> {
+        Ok(Vec::<T>::from_items(items)?.into_iter().collect())
+    }
+}
+
+impl<T> Multiple for HashSet<T>
+where
+    T: ConvertParsed + Hash + Eq,
+    T::Type: Parse,
+{
+    type Item = T::Type;
+
+    fn parse_items(input: ParseStream) -> syn::Result<Vec<(Self::Item, Span)>> {
+        Vec::<T>::parse_items(input)
+    }
+
+    fn from_items(items: Vec<PathValue<Self::Item>>) -> Result<Self, Error> {
+        Ok(Vec::<T>::from_items(items)?.into_iter().collect())
+    }
+}
+
+impl<K, V> Multiple for Map<K, V>
+where
+    K: ConvertParsed,
+    V: ConvertParsed,
+    K::Type: Parse,
+    V::Type: Parse,
+{
+    type Item = Pair<K::Type, V::Type>;
+
+    fn parse_items(input: ParseStream) -> syn::Result<Vec<(Self::Item, Span)>> {
+        if input.peek(token::Brace) {
+            let content;
+            braced!(content in input);
+            parse_elements(&content)
+        } else {
+            let span = input.span();
+            Ok(vec![(input.parse()?, span)])
+        }
+    }
+
+    fn from_items(items: Vec<PathValue<Self::Item>>) -> Result<Self, Error> {
+        let pairs = items.into_iter().map(|PathValue { path, value }| {
+            let Pair { key, value, .. } = value;
+
+            match (
+                K::convert(PathValue { path, value: key }),
+                V::convert(PathValue { path, value }),
+            ) {
+                (Ok(k), Ok(v)) => Ok((k, v)),
+                (Err(e), _) | (_, Err(e)) => Err(e),
+            }
+        });
+
+        Ok(Map(collect_results(pairs)?))
+    }
+}
+
+/// Parse comma separated elements until the end of the input, with the span of each element.
+fn parse_elements<T: Parse>(input: ParseStream) -> syn::Result<Vec<(T, Span)>> {
+    let mut elements = Vec::new();
+
+    while !input.is_empty() {
+        let span = input.span();
+        elements.push((input.parse()?, span));
+
+        if input.is_empty() {
+            break;
+        }
+
+        input.parse::<Token![,]>()?;
+    }
+
+    Ok(elements)
+}
+
+fn collect_results<T>(results: impl Iterator<Item = Result<T, Error>>) -> Result<Vec<T>, Error> {
+    let mut values = Vec::new();
+    let mut errors = Diagnostics::new();
+
+    results.for_each(|result| match result {
+        Ok(o) => values.push(o),
+        Err(e) => errors.push(e),
+    });
+
+    errors.finish(values)
+}
@@ -0,0 +1,67 @@
+use proc_macro2::Span;
+use syn::{
+    meta::ParseNestedMeta,
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    MetaList,
+};
+
+use crate::{suggestion::unknown_ident_msg, AttributeIdent, Error, FromAttr, ParseMeta};
+
+/// The parsed but not yet converted value of a nested [`FromAttr`](trait@crate::FromAttr) type.
+///
+/// Used as [`ConvertParsed::Type`](crate::ConvertParsed::Type) by types with `#[attribute(nested)]`,
+/// it is parsed from `b = inner(...)` or from the keyless form `b(...)`.
+pub struct Nested<T: FromAttr> {
+    span: Span,
+    parser: T::Parser,
+    // the error of parsing the list, kept to be returned with its kinds on conversion
+    error: Option<Error>,
+}
+
+impl<T: FromAttr> Nested<T> {
+    /// Parse the keyless form `b(...)`, where the path of the meta acts as the list path.
+    pub fn parse_list(meta: &ParseNestedMeta) -> Result<Self, Error> {
+        let mut parser = T::Parser::default();
+        parser.parse_nested(meta)?;
+
+        Ok(Self {
+            span: meta.path.span(),
+            parser,
+            error: None,
+        })
+    }
+
+    /// Convert to the value with [`FromAttr::from_parser`](crate::FromAttr::from_parser).
+    pub fn into_value(self) -> Result<T, Error> {
+        match self.error {
+            Some(err) => Err(err),
+            None => T::from_parser(self.parser, &[self.span]),
+        }
+    }
+}
+
+impl<T> Parse for Nested<T>
+where
+    T: FromAttr + AttributeIdent,
+{
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let list = input.parse::<MetaList>()?;
+
+        if !T::is_ident(&list.path) {
+            return Err(syn::Error::new(
+                list.path.span(),
+                unknown_ident_msg(&list.path, T::IDENTS),
+            ));
+        }
+
+        let mut parser = T::Parser::default();
+        let error = parser.parse_meta_list(&list).err();
+
+        Ok(Self {
+            span: list.span(),
+            parser,
+            error,
+        })
+    }
+}
@@ -1,8 +1,16 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::ToTokens;
 use syn::{
+    ext::IdentExt,
     meta::{self, ParseNestedMeta},
-    parse::Parser,
-    Attribute, Meta, MetaList,
+    parenthesized,
+    parse::{ParseBuffer, ParseStream, Parser},
+    token, Attribute, Expr, Ident, MacroDelimiter, Meta, MetaList, MetaNameValue, Path, Token,
+};
+
+use crate::{
+    suggestion::{path_to_string, unknown_argument_msg},
+    Diagnostics, Error, ErrorKind,
 };
 
 /// Used to enable parsing of each [`meta`](syn::meta::ParseNestedMeta).
@@ -10,34 +18,360 @@ use syn::{
 /// Generally an helper struct is generated from the derive macro [`FromAttr`](macro@crate::FromAttr),
 /// and an implementation of the [`ParseMeta`] trait is generated on this struct.
 pub trait ParseMeta {
+    /// The names of the named arguments accepted by the type, including the flattened ones.
+    const FIELDS: &'static [&'static str] = &[];
+
     /// Whether the type is unit type.
     fn is_unit() -> bool;
 
+    /// Whether the path is the name or an alias of a named argument accepted by the type.
+    fn is_field(path: &Path) -> bool {
+        Self::FIELDS.iter().any(|field| path.is_ident(field))
+    }
+
+    /// Whether the type accepts positional arguments.
+    fn has_positional() -> bool {
+        false
+    }
+
+    /// Called before the arguments of each [`Attribute`] are parsed, keys given again in a later
+    /// attribute are merged according to the `merge` strategy instead of being duplicates.
+    fn start_attribute(&mut self) {}
+
     /// Parse one [`meta`](syn::meta::ParseNestedMeta).
-    fn parse_meta(&mut self, meta: ParseNestedMeta) -> syn::Result<()>;
+    fn parse_meta(&mut self, meta: ParseNestedMeta) -> Result<(), Error>;
+
+    /// Returns the span of the path of the named argument `name`, if it has been parsed.
+    fn field_span(&self, name: &str) -> Option<Span> {
+        let _ = name;
+        None
+    }
+
+    /// Parse the leading positional arguments, stops at the first named argument.
+    fn parse_positional(&mut self, input: ParseStream) -> Result<(), Error> {
+        let _ = input;
+        Ok(())
+    }
+
+    /// Parse one [`ParseStream`], the positional arguments followed by the named arguments.
+    fn parse_stream(&mut self, input: ParseStream) -> Result<(), Error> {
+        self.parse_positional(input)?;
+
+        let rest = input.parse::<TokenStream>()?;
+        let mut errors = Diagnostics::new();
+        let result = meta::parser(|meta| parse_or_skip(self, meta, &mut errors)).parse2(rest);
+        combine_errors(result, errors)
+    }
 
     /// Parse one [`Attribute`].
-    fn parse_attr(&mut self, attr: &Attribute) -> syn::Result<()> {
+    fn parse_attr(&mut self, attr: &Attribute) -> Result<(), Error> {
+        self.start_attribute();
+
         if Self::is_unit() {
-            attr.meta.require_path_only().map(|_| ())
+            attr.meta.require_path_only()?;
+            Ok(())
         } else if matches!(attr.meta, Meta::Path(_)) {
             // #[one(a, b, c)]
             // if `a`, `b`, `c` all have default value,
             // it should be allowed to be written as `#[one]`
             // instead of having to be written as `#[one()]`
             Ok(())
+        } else if Self::has_positional() {
+            let mut result = Ok(());
+            let parsed = attr.parse_args_with(|input: ParseStream| {
+                result = self.parse_stream(input);
+                Ok(())
+            });
+            result.and(parsed.map_err(Error::from))
         } else {
-            attr.parse_nested_meta(|meta| self.parse_meta(meta))
+            let mut errors = Diagnostics::new();
+            let result = attr.parse_nested_meta(|meta| parse_or_skip(self, meta, &mut errors));
+            combine_errors(result, errors)
         }
     }
 
     /// Parse one [`MetaList`].
-    fn parse_meta_list(&mut self, meta_list: &MetaList) -> syn::Result<()> {
-        meta_list.parse_nested_meta(|meta| self.parse_meta(meta))
+    fn parse_meta_list(&mut self, meta_list: &MetaList) -> Result<(), Error> {
+        if Self::has_positional() {
+            let mut result = Ok(());
+            let parsed = meta_list.parse_args_with(|input: ParseStream| {
+                result = self.parse_stream(input);
+                Ok(())
+            });
+            result.and(parsed.map_err(Error::from))
+        } else {
+            let mut errors = Diagnostics::new();
+            let result = meta_list.parse_nested_meta(|meta| parse_or_skip(self, meta, &mut errors));
+            combine_errors(result, errors)
+        }
+    }
+
+    /// Parse the list of one nested [`meta`](syn::meta::ParseNestedMeta), like the `(b = 1)` of `a(b = 1)`.
+    fn parse_nested(&mut self, meta: &ParseNestedMeta) -> Result<(), Error> {
+        if Self::has_positional() {
+            let content = parenthesized_content(meta.input)?;
+            self.parse_stream(&content)
+        } else {
+            // unlike `ParseNestedMeta::parse_nested_meta`, an empty list like `a()` is accepted
+            let content = parenthesized_content(meta.input)?;
+            let mut errors = Diagnostics::new();
+            let result = meta::parser(|meta| parse_or_skip(self, meta, &mut errors))
+                .parse2(content.parse()?);
+            combine_errors(result, errors)
+        }
     }
 
     /// Parse one [`TokenStream`].
-    fn parse_tokens(&mut self, tokens: TokenStream) -> syn::Result<()> {
-        meta::parser(|meta| self.parse_meta(meta)).parse2(tokens)
+    fn parse_tokens(&mut self, tokens: TokenStream) -> Result<(), Error> {
+        if Self::has_positional() {
+            let mut result = Ok(());
+            let parsed = (|input: ParseStream| {
+                result = self.parse_stream(input);
+                Ok(())
+            })
+            .parse2(tokens);
+            result.and(parsed.map_err(Error::from))
+        } else {
+            let mut errors = Diagnostics::new();
+            let result = meta::parser(|meta| parse_or_skip(self, meta, &mut errors)).parse2(tokens);
+            combine_errors(result, errors)
+        }
     }
 }
+
+/// Parse one [`meta`](syn::meta::ParseNestedMeta), an error is collected and the rest of the meta
+/// is skipped, so that the following metas are still parsed.
+fn parse_or_skip<P: ParseMeta + ?Sized>(
+    parser: &mut P,
+    meta: ParseNestedMeta,
+    errors: &mut Diagnostics,
+) -> syn::Result<()> {
+    let input = meta.input;
+
+    if let Err(err) = parser.parse_meta(meta) {
+        errors.push(err);
+
+        while !input.is_empty() && !input.peek(Token![,]) {
+            input.parse::<TokenTree>()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The content of the parentheses at the start of the input.
+fn parenthesized_content<'a>(input: ParseStream<'a>) -> syn::Result<ParseBuffer<'a>> {
+    let content;
+    parenthesized!(content in input);
+    Ok(content)
+}
+
+/// Combine the collected errors with the error that stopped the parsing, if any.
+fn combine_errors(result: syn::Result<()>, mut errors: Diagnostics) -> Result<(), Error> {
+    if let Err(err) = result {
+        errors.push(err);
+    }
+
+    errors.finish(())
+}
+
+/// Concatenate two lists of field names in const context, `N` must be the sum of their lengths.
+#[doc(hidden)]
+pub const fn concat_fields<const N: usize>(
+    a: &[&'static str],
+    b: &[&'static str],
+) -> [&'static str; N] {
+    let mut fields = [""; N];
+
+    let mut i = 0;
+    while i < a.len() {
+        fields[i] = a[i];
+        i += 1;
+    }
+
+    let mut j = 0;
+    while j < b.len() {
+        fields[i + j] = b[j];
+        j += 1;
+    }
+
+    fields
+}
+
+/// Whether `name` is one of the `fields`, used to check the field names at compile time.
+#[doc(hidden)]
+pub const fn contains_field(fields: &[&str], name: &str) -> bool {
+    let name = name.as_bytes();
+
+    let mut i = 0;
+    'fields: while i < fields.len() {
+        let field = fields[i].as_bytes();
+        i += 1;
+
+        if field.len() != name.len() {
+            continue;
+        }
+
+        let mut j = 0;
+        while j < name.len() {
+            if field[j] != name[j] {
+                continue 'fields;
+            }
+            j += 1;
+        }
+
+        return true;
+    }
+
+    false
+}
+
+/// Build the error message for an unknown named argument.
+#[doc(hidden)]
+pub fn unknown_field_msg(path: &Path, fields: &[&str]) -> String {
+    match fields {
+        [] => "unexpected named argument, only positional arguments are accepted".to_string(),
+        [_] => unknown_argument_msg(path, fields, "expected field"),
+        _ => unknown_argument_msg(path, fields, "expected fields"),
+    }
+}
+
+/// The error for an unknown named argument, with the message built by [`unknown_field_msg`].
+#[doc(hidden)]
+pub fn unknown_field_error(meta: &ParseNestedMeta, msg: String) -> Error {
+    Error::new(
+        ErrorKind::UnknownField {
+            name: path_to_string(&meta.path),
+        },
+        meta.error(msg),
+    )
+}
+
+/// Whether the input starts with a named argument rather than a positional one.
+///
+/// `a = ...` and `a(...)` are always named arguments, a bare `a` is only a named argument
+/// when `is_field` returns `true` for `a`.
+#[doc(hidden)]
+pub fn is_named_argument(input: ParseStream, is_field: fn(&Path) -> bool) -> bool {
+    let fork = input.fork();
+
+    let path = if fork.peek(Ident::peek_any) && !fork.peek2(Token![::]) {
+        match fork.call(Ident::parse_any) {
+            Ok(ident) => Path::from(ident),
+            Err(_) => return false,
+        }
+    } else {
+        match fork.call(Path::parse_mod_style) {
+            Ok(path) => path,
+            Err(_) => return false,
+        }
+    };
+
+    if fork.peek(Token![=]) || fork.peek(token::Paren) {
+        return true;
+    }
+
+    (fork.is_empty() || fork.peek(Token![,])) && is_field(&path)
+}
+
+/// Parse one [`meta`](syn::meta::ParseNestedMeta) into `parser` without consuming it,
+/// returns the number of token trees that the value of the meta occupies.
+///
+/// Used when it is not yet known which of several parsers the meta belongs to.
+#[doc(hidden)]
+pub fn parse_meta_speculatively<P: ParseMeta>(
+    parser: &mut P,
+    meta: &ParseNestedMeta,
+) -> Result<usize, Error> {
+    let rest = meta.input.fork().parse::<TokenStream>()?;
+    let total = rest.clone().into_iter().count();
+
+    let mut tokens = meta.path.to_token_stream();
+    tokens.extend(rest);
+
+    let mut remaining = 0;
+    let mut result = Ok(());
+
+    // the rest of the input is consumed on success, so the closure is only called once
+    let parsed_tokens = meta::parser(|meta| {
+        let input = meta.input;
+
+        // the error is kept with its kind, parsing stops with a copy of it
+        if let Err(err) = parser.parse_meta(meta) {
+            let stop = syn::Error::from(err.clone());
+            result = Err(err);
+            return Err(stop);
+        }
+
+        if !input.is_empty() && !input.peek(Token![,]) {
+            return Err(input.error("expected `,`"));
+        }
+
+        remaining = input.parse::<TokenStream>()?.into_iter().count();
+        Ok(())
+    })
+    .parse2(tokens);
+    result?;
+    parsed_tokens?;
+
+    Ok(total - remaining)
+}
+
+/// Parse the rest of an unknown [`meta`](syn::meta::ParseNestedMeta) into a [`Meta`],
+/// consuming its value.
+#[doc(hidden)]
+pub fn parse_unknown_meta(meta: &ParseNestedMeta) -> syn::Result<Meta> {
+    // `Path` is not `Clone` without the `clone-impls` feature of syn
+    let path = match meta.path.get_ident() {
+        Some(ident) => Path::from(ident.clone()),
+        None => Path::parse_mod_style.parse2(meta.path.to_token_stream())?,
+    };
+    let input = meta.input;
+
+    if input.peek(token::Paren) {
+        let TokenTree::Group(group) = input.parse::<TokenTree>()? else {
+            unreachable!("the input starts with parentheses");
+        };
+
+        return Ok(Meta::List(MetaList {
+            path,
+            delimiter: MacroDelimiter::Paren(token::Paren {
+                span: group.delim_span(),
+            }),
+            tokens: group.stream(),
+        }));
+    }
+
+    if input.peek(Token![=]) {
+        let eq_token = input.parse::<Token![=]>()?;
+        let mut tokens = TokenStream::new();
+
+        while !input.is_empty() && !input.peek(Token![,]) {
+            tokens.extend([input.parse::<TokenTree>()?]);
+        }
+
+        if tokens.is_empty() {
+            return Err(input.error("expected a value after `=`"));
+        }
+
+        let value = syn::parse2::<Expr>(tokens.clone()).unwrap_or(Expr::Verbatim(tokens));
+
+        return Ok(Meta::NameValue(MetaNameValue {
+            path,
+            eq_token,
+            value,
+        }));
+    }
+
+    Ok(Meta::Path(path))
+}
+
+/// Skip `n` token trees of the input.
+#[doc(hidden)]
+pub fn skip_token_trees(input: ParseStream, n: usize) -> syn::Result<()> {
+    for _ in 0..n {
+        input.parse::<TokenTree>()?;
+    }
+
+    Ok(())
+}
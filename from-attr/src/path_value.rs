@@ -1,6 +1,7 @@
 use proc_macro2::Span;
+use syn::meta::ParseNestedMeta;
 
-use crate::ConvertParsed;
+use crate::{ConvertParsed, Error};
 
 /// Represents the path-value pair parsed from the [`meta`](syn::meta::ParseNestedMeta).
 pub struct PathValue<T> {
@@ -16,7 +17,9 @@ where
 {
     type Type = T::Type;
 
-    fn convert(path_value: PathValue<Self::Type>) -> syn::Result<Self> {
+    const APPENDABLE: bool = T::APPENDABLE;
+
+    fn convert(path_value: PathValue<Self::Type>) -> Result<Self, Error> {
         Ok(PathValue {
             path: path_value.path,
             value: T::convert(path_value)?,
@@ -26,4 +29,12 @@ where
     fn flag() -> Option<Self::Type> {
         T::flag()
     }
+
+    fn list(meta: &ParseNestedMeta) -> Option<Result<Self::Type, Error>> {
+        T::list(meta)
+    }
+
+    fn append(value: Self::Type, other: Self::Type) -> Option<Self::Type> {
+        T::append(value, other)
+    }
 }
@@ -0,0 +1,89 @@
+use proc_macro2::Span;
+use syn::meta::ParseNestedMeta;
+
+use crate::{spanned_type::SpannedType, ConvertParsed, Error, PathValue};
+
+/// A value with the spans of the meta it is parsed from.
+///
+/// Unlike [`PathValue`], the span of the value is kept after the conversion, like the span of the
+/// [`LitStr`](struct@syn::LitStr) a [`String`] is converted from. Can be used inside [`Option`],
+/// [`Vec`] and [`Map`](crate::Map).
+#[derive(Clone, Copy, Debug)]
+pub struct Spanned<T> {
+    /// The path of the meta.
+    pub path: Span,
+    /// The span of the value, or of the path for a flag.
+    pub value_span: Span,
+    /// The span from the path to the value, or the span of the path if they cannot be joined.
+    pub span: Span,
+    /// The value converted from the meta.
+    pub value: T,
+}
+
+impl<T: Default> Default for Spanned<T> {
+    fn default() -> Self {
+        Self {
+            path: Span::call_site(),
+            value_span: Span::call_site(),
+            span: Span::call_site(),
+            value: T::default(),
+        }
+    }
+}
+
+impl<T> ConvertParsed for Spanned<T>
+where
+    T: ConvertParsed,
+{
+    type Type = SpannedType<T::Type>;
+
+    const APPENDABLE: bool = T::APPENDABLE;
+
+    fn convert(path_value: PathValue<Self::Type>) -> Result<Self, Error> {
+        let PathValue { path, value } = path_value;
+        let value_span = value.span.unwrap_or(path);
+
+        Ok(Self {
+            path,
+            value_span,
+            span: path.join(value_span).unwrap_or(path),
+            value: T::convert(PathValue {
+                path,
+                value: value.value,
+            })?,
+        })
+    }
+
+    fn default() -> Option<Self> {
+        T::default().map(|value| Self {
+            path: Span::call_site(),
+            value_span: Span::call_site(),
+            span: Span::call_site(),
+            value,
+        })
+    }
+
+    fn flag() -> Option<Self::Type> {
+        T::flag().map(|value| SpannedType { value, span: None })
+    }
+
+    fn list(meta: &ParseNestedMeta) -> Option<Result<Self::Type, Error>> {
+        let span = meta.input.span();
+
+        T::list(meta).map(|value| {
+            value.map(|value| SpannedType {
+                value,
+                span: Some(span),
+            })
+        })
+    }
+
+    fn append(value: Self::Type, other: Self::Type) -> Option<Self::Type> {
+        let span = match (value.span, other.span) {
+            (Some(a), Some(b)) => Some(a.join(b).unwrap_or(a)),
+            (a, b) => a.or(b),
+        };
+
+        T::append(value.value, other.value).map(|value| SpannedType { value, span })
+    }
+}
@@ -0,0 +1,41 @@
+use proc_macro2::Span;
+use syn::parse::{Parse, ParseStream};
+
+pub struct SpannedType<T> {
+    pub(crate) value: T,
+    /// The span of the tokens of the value, `None` for a flag.
+    pub(crate) span: Option<Span>,
+}
+
+impl<T: Parse> Parse for SpannedType<T> {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let start = input.cursor();
+        let value = input.parse()?;
+        let end = input.cursor();
+
+        // the span of the first and the last token tree of the value
+        let mut first = None;
+        let mut last = None;
+        let mut cursor = start;
+
+        while cursor != end {
+            let Some((token_tree, next)) = cursor.token_tree() else {
+                break;
+            };
+
+            first.get_or_insert(token_tree.span());
+            last = Some(token_tree.span());
+            cursor = next;
+        }
+
+        let span = match (first, last) {
+            (Some(first), Some(last)) => first.join(last).unwrap_or(first),
+            _ => input.span(),
+        };
+
+        Ok(Self {
+            value,
+            span: Some(span),
+        })
+    }
+}
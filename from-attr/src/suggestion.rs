@@ -0,0 +1,116 @@
+use quote::ToTokens;
+use syn::Path;
+
+/// The most names listed in an error message, the others are only counted.
+const MAX_LISTED_NAMES: usize = 8;
+
+/// Find the candidate closest to `name`, if it is close enough to be a likely typo.
+///
+/// Names are compared case-insensitively, a candidate is close enough when at most a third of
+/// the characters have to be edited.
+#[doc(hidden)]
+pub fn did_you_mean<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let name = name.to_lowercase();
+    let max_distance = name.chars().count().max(3) / 3;
+
+    candidates
+        .iter()
+        .map(|candidate| (*candidate, edit_distance(&name, &candidate.to_lowercase())))
+        .filter(|(_, distance)| *distance <= max_distance)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(candidate, _)| candidate)
+}
+
+/// Join the names as "`a`, `b`, `c`", only listing the first names of a long list.
+#[doc(hidden)]
+pub fn join_names(names: &[&str]) -> String {
+    let listed = names
+        .iter()
+        .take(MAX_LISTED_NAMES)
+        .map(|name| format!("`{}`", name))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    match names.len().saturating_sub(MAX_LISTED_NAMES) {
+        0 => listed,
+        1 => format!("{} and 1 other", listed),
+        others => format!("{} and {} others", listed, others),
+    }
+}
+
+/// Message for an argument that is not one of the `candidates`.
+#[doc(hidden)]
+pub fn unknown_argument_msg(path: &Path, candidates: &[&str], expected: &str) -> String {
+    let name = path_to_string(path);
+
+    match did_you_mean(&name, candidates) {
+        Some(candidate) => format!("unknown argument `{}`, did you mean `{}`?", name, candidate),
+        None => format!("{}: {}", expected, join_names(candidates)),
+    }
+}
+
+/// Message for an attribute whose ident is not one of the `idents`.
+#[doc(hidden)]
+pub fn unknown_ident_msg(path: &Path, idents: &[&str]) -> String {
+    let name = path_to_string(path);
+
+    match did_you_mean(&name, idents) {
+        Some(ident) => format!("unknown ident `{}`, did you mean `{}`?", name, ident),
+        None => format!("expected idents: {}", join_names(idents)),
+    }
+}
+
+/// Message for an ident that is not one of the `idents` accepted by a [`FromIdent`](macro@crate::FromIdent) enum.
+#[doc(hidden)]
+pub fn invalid_ident_msg(ident: &str, idents: &[&str]) -> String {
+    match did_you_mean(ident, idents) {
+        Some(valid) => format!("invalid ident: `{}`, did you mean `{}`?", ident, valid),
+        None => format!(
+            "invalid ident: `{}`, valid idents: {}",
+            ident,
+            join_names(idents)
+        ),
+    }
+}
+
+pub(crate) fn path_to_string(path: &Path) -> String {
+    match path.get_ident() {
+        Some(ident) => ident.to_string(),
+        None => path.to_token_stream().to_string().replace(' ', ""),
+    }
+}
+
+/// The edit distance between `a` and `b`, where swapping two adjacent characters is one edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+
+    // `distances[i][j]` is the distance between the first `i` chars of `a` and `j` chars of `b`
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
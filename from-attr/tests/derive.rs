@@ -1,4 +1,6 @@
-use from_attr::{convert_parsed_from_meta_list, FlagOrValue, FromAttr, Map};
+//! Tests for the derive macros.
+
+use from_attr::{convert_parsed_from_meta_list, FlagOrValue, FromAttr, Map, Spanned};
 use from_attr_macro::FromIdent;
 use quote::quote;
 use syn::{parse_quote, Expr, LitStr, Type};
@@ -160,6 +162,64 @@ fn nested() {
     );
 }
 
+#[test]
+fn nested_attribute() {
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [inner], nested)]
+    struct Inner {
+        #[attribute(conflicts = [b])]
+        a: usize,
+        #[attribute(default)]
+        b: usize,
+    }
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [outer])]
+    struct Outer {
+        a: usize,
+        b: Inner,
+        c: Option<Inner>,
+    }
+
+    let attrs = [parse_quote!(#[outer(a = 1, b = inner(a = 10))])];
+
+    assert_eq!(
+        Outer::from_attributes(&attrs).unwrap().unwrap().value,
+        Outer {
+            a: 1,
+            b: Inner { a: 10, b: 0 },
+            c: None
+        }
+    );
+
+    let attrs = [parse_quote!(#[outer(a = 1, b(a = 10), c(a = 20))])];
+
+    assert_eq!(
+        Outer::from_attributes(&attrs).unwrap().unwrap().value,
+        Outer {
+            a: 1,
+            b: Inner { a: 10, b: 0 },
+            c: Some(Inner { a: 20, b: 0 })
+        }
+    );
+
+    // an empty list goes through the defaults and missing fields of the inner type
+    let attrs = [parse_quote!(#[outer(a = 1, b())])];
+
+    let err = Outer::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(err.to_string(), "missing `a` field, try `a = ...`");
+
+    let attrs = [parse_quote!(#[outer(a = 1, b(a = 10, b = 20))])];
+
+    let err = Outer::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(err.to_string(), "`a` conflicts with mutually exclusive `b`");
+
+    let attrs = [parse_quote!(#[outer(a = 1, b = other(a = 10))])];
+
+    let err = Outer::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(err.to_string(), "expected idents: `inner`");
+}
+
 #[test]
 fn conflicts() {
     #[allow(dead_code)]
@@ -234,3 +294,1730 @@ fn map() {
         }
     );
 }
+
+#[test]
+fn enum_variants() {
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [route])]
+    enum Route {
+        Get,
+        Post {
+            path: String,
+            #[attribute(default)]
+            body: bool,
+        },
+        #[attribute(rename = "remove")]
+        Delete,
+        HTTPHead,
+    }
+
+    let attrs = [parse_quote!(#[route(get)])];
+
+    assert_eq!(
+        Route::from_attributes(&attrs).unwrap().unwrap().value,
+        Route::Get
+    );
+
+    let attrs = [parse_quote!(#[route(post(path = "/y", body))])];
+
+    assert_eq!(
+        Route::from_attributes(&attrs).unwrap().unwrap().value,
+        Route::Post {
+            path: "/y".to_string(),
+            body: true
+        }
+    );
+
+    let attrs = [parse_quote!(#[route(remove)])];
+
+    assert_eq!(
+        Route::from_attributes(&attrs).unwrap().unwrap().value,
+        Route::Delete
+    );
+
+    // acronyms are one word
+    let attrs = [parse_quote!(#[route(http_head)])];
+
+    assert_eq!(
+        Route::from_attributes(&attrs).unwrap().unwrap().value,
+        Route::HTTPHead
+    );
+
+    let attrs = [parse_quote!(#[route(get, post(path = "/y"))])];
+
+    let err = Route::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(err.into_iter().count(), 2);
+
+    let attrs = [parse_quote!(#[route(post)])];
+
+    assert!(Route::from_attributes(&attrs).is_err());
+
+    // an empty list reports the missing fields of the variant
+    let attrs = [parse_quote!(#[route(post())])];
+
+    let err = Route::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(err.to_string(), "missing `path` field, try `path = ...`");
+
+    let attrs = [parse_quote!(#[route])];
+
+    assert!(Route::from_attributes(&attrs).is_err());
+}
+
+#[test]
+fn tagged_enum() {
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [storage], tag = "kind")]
+    enum Storage {
+        File {
+            #[attribute(alias = "p")]
+            path: String,
+            #[attribute(default)]
+            sync: bool,
+        },
+        Memory {
+            #[attribute(default = 16)]
+            capacity: usize,
+            path: Option<String>,
+        },
+        Null,
+    }
+
+    let attrs = [parse_quote!(#[storage(kind = "file", path = "a.db")])];
+
+    assert_eq!(
+        Storage::from_attributes(&attrs).unwrap().unwrap().value,
+        Storage::File {
+            path: "a.db".to_string(),
+            sync: false
+        }
+    );
+
+    // the tag can be given after the fields
+    let attrs = [parse_quote!(#[storage(capacity = 10, kind = "memory")])];
+
+    assert_eq!(
+        Storage::from_attributes(&attrs).unwrap().unwrap().value,
+        Storage::Memory {
+            capacity: 10,
+            path: None
+        }
+    );
+
+    let attrs = [parse_quote!(#[storage(kind = "null")])];
+
+    assert_eq!(
+        Storage::from_attributes(&attrs).unwrap().unwrap().value,
+        Storage::Null
+    );
+
+    let attrs = [parse_quote!(#[storage(kind = "file", p = "b.db")])];
+
+    assert_eq!(
+        Storage::from_attributes(&attrs).unwrap().unwrap().value,
+        Storage::File {
+            path: "b.db".to_string(),
+            sync: false
+        }
+    );
+
+    let attrs = [parse_quote!(#[storage(kind = "file", sync, sync, path = "a.db")])];
+
+    let err = Storage::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(err.to_string(), "duplicate `sync` argument");
+
+    let attrs = [parse_quote!(#[storage(kind = "memory", p = "b.db")])];
+
+    let err = Storage::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(err.to_string(), "`p` is not valid for kind = memory");
+
+    let attrs = [parse_quote!(#[storage(kind = "file", path = "a.db", capacity = 10)])];
+
+    let err = Storage::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(err.to_string(), "`capacity` is not valid for kind = file");
+
+    let attrs = [parse_quote!(#[storage(kind = "disk")])];
+
+    assert!(Storage::from_attributes(&attrs).is_err());
+
+    let attrs = [parse_quote!(#[storage(path = "a.db")])];
+
+    assert!(Storage::from_attributes(&attrs).is_err());
+}
+
+#[test]
+fn positional() {
+    #[derive(FromIdent, PartialEq, Eq, Debug)]
+    enum Method {
+        Get,
+        Post,
+    }
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [route])]
+    struct Route {
+        #[attribute(positional)]
+        path: String,
+        #[attribute(default = Method::Get)]
+        method: Method,
+    }
+
+    let attrs = [parse_quote!(#[route("/users/:id", method = Post)])];
+
+    assert_eq!(
+        Route::from_attributes(&attrs).unwrap().unwrap().value,
+        Route {
+            path: "/users/:id".to_string(),
+            method: Method::Post
+        }
+    );
+
+    // positional fields can still be given by name
+    let attrs = [parse_quote!(#[route(path = "/users")])];
+
+    assert_eq!(
+        Route::from_attributes(&attrs).unwrap().unwrap().value,
+        Route {
+            path: "/users".to_string(),
+            method: Method::Get
+        }
+    );
+
+    let attrs = [parse_quote!(#[route("/users", "/posts")])];
+
+    let err = Route::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.to_string(),
+        "unexpected positional argument, expected at most 1"
+    );
+
+    let attrs = [parse_quote!(#[route(method = Post)])];
+
+    let err = Route::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(err.to_string(), "missing `path` field, try `path = ...`");
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [rename])]
+    struct Rename(String, #[attribute(default)] Option<usize>);
+
+    let attrs = [parse_quote!(#[rename("new_name")])];
+
+    assert_eq!(
+        Rename::from_attributes(&attrs).unwrap().unwrap().value,
+        Rename("new_name".to_string(), None)
+    );
+
+    let tokens = quote!("new_name", 2);
+
+    assert_eq!(
+        Rename::from_tokens(tokens).unwrap(),
+        Rename("new_name".to_string(), Some(2))
+    );
+
+    let attrs = [parse_quote!(#[rename(name = "new_name")])];
+
+    assert!(Rename::from_attributes(&attrs).is_err());
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [handler])]
+    enum Handler {
+        Get(String),
+        Head,
+    }
+
+    let attrs = [parse_quote!(#[handler(get("/users"))])];
+
+    assert_eq!(
+        Handler::from_attributes(&attrs).unwrap().unwrap().value,
+        Handler::Get("/users".to_string())
+    );
+}
+
+#[test]
+fn flatten() {
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [common])]
+    struct Common {
+        #[attribute(rename = "crate", default)]
+        krate: Option<String>,
+        #[attribute(default)]
+        doc: bool,
+    }
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [outer])]
+    struct Outer {
+        name: String,
+        #[attribute(default, conflicts = [doc])]
+        hidden: bool,
+        #[attribute(flatten)]
+        common: Common,
+    }
+
+    let attrs = [parse_quote!(#[outer(name = "a", crate = "my_crate", doc)])];
+
+    assert_eq!(
+        Outer::from_attributes(&attrs).unwrap().unwrap().value,
+        Outer {
+            name: "a".to_string(),
+            hidden: false,
+            common: Common {
+                krate: Some("my_crate".to_string()),
+                doc: true
+            }
+        }
+    );
+
+    let attrs = [parse_quote!(#[outer(name = "a")])];
+
+    assert_eq!(
+        Outer::from_attributes(&attrs).unwrap().unwrap().value,
+        Outer {
+            name: "a".to_string(),
+            hidden: false,
+            common: Common {
+                krate: None,
+                doc: false
+            }
+        }
+    );
+
+    let attrs = [parse_quote!(#[outer(name = "a", vis = "pub")])];
+
+    let err = Outer::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.to_string(),
+        "expected fields: `name`, `hidden`, `crate`, `doc`"
+    );
+
+    let attrs = [parse_quote!(#[outer(name = "a", hidden, doc)])];
+
+    let err = Outer::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.to_string(),
+        "`doc` conflicts with mutually exclusive `hidden`"
+    );
+}
+
+#[test]
+fn validate() {
+    use from_attr::PathValue;
+    use proc_macro2::Span;
+
+    fn not_zero(value: PathValue<&usize>) -> syn::Result<()> {
+        if *value.value == 0 {
+            Err(syn::Error::new(value.path, "must not be zero"))
+        } else {
+            Ok(())
+        }
+    }
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [range], validate = Range::check)]
+    struct Range {
+        #[attribute(default = 1, validate = not_zero)]
+        start: usize,
+        #[attribute(validate = not_zero)]
+        end: usize,
+    }
+
+    impl Range {
+        fn check(&self, spans: &[Span]) -> syn::Result<()> {
+            if self.start > self.end {
+                Err(syn::Error::new(spans[0], "`start` must not exceed `end`"))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    let attrs = [parse_quote!(#[range(start = 2, end = 3)])];
+
+    assert_eq!(
+        Range::from_attributes(&attrs).unwrap().unwrap().value,
+        Range { start: 2, end: 3 }
+    );
+
+    let attrs = [parse_quote!(#[range(start = 0, end = 0)])];
+
+    let err = Range::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.into_iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+        ["must not be zero", "must not be zero"]
+    );
+
+    let attrs = [parse_quote!(#[range(start = 3, end = 2)])];
+
+    let err = Range::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(err.to_string(), "`start` must not exceed `end`");
+}
+
+#[test]
+fn builtin_validators() {
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [test])]
+    struct Test {
+        #[attribute(default, range = 1..=64)]
+        size: usize,
+        #[attribute(default, non_empty, max_len = 8)]
+        name: Option<String>,
+        #[attribute(default, non_empty, unique)]
+        tags: Vec<String>,
+        #[attribute(default, pattern = ident)]
+        ident: Option<String>,
+    }
+
+    let attrs = [parse_quote!(#[test(size = 64, name = "a", tags = ["a", "b"], ident = "a")])];
+
+    assert_eq!(
+        Test::from_attributes(&attrs).unwrap().unwrap().value,
+        Test {
+            size: 64,
+            name: Some("a".to_string()),
+            tags: vec!["a".to_string(), "b".to_string()],
+            ident: Some("a".to_string())
+        }
+    );
+
+    let attrs = [parse_quote!(#[test(size = 0, name = "", tags = [], ident = "fn")])];
+
+    let err = Test::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.into_iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+        [
+            "`size` must be in the range 1..=64",
+            "`name` must not be empty",
+            "`tags` must not be empty",
+            "`ident` must be a valid Rust identifier"
+        ]
+    );
+
+    let attrs = [parse_quote!(#[test(name = "too long name", tags = ["a", "a"])])];
+
+    let err = Test::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.into_iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+        [
+            "`name` must have a length of at most 8",
+            "`tags` must not contain duplicate elements"
+        ]
+    );
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [level])]
+    struct Level {
+        #[attribute(flag = None, range = 1..=3)]
+        level: Option<u8>,
+    }
+
+    let attrs = [parse_quote!(#[level(level)])];
+
+    assert_eq!(
+        Level::from_attributes(&attrs).unwrap().unwrap().value,
+        Level { level: None }
+    );
+
+    let attrs = [parse_quote!(#[level(level = 4)])];
+
+    let err = Level::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(err.to_string(), "`level` must be in the range 1..=3");
+}
+
+#[test]
+fn requires() {
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [test])]
+    struct Test {
+        #[attribute(requires = [password])]
+        user: Option<String>,
+        password: Option<String>,
+        #[attribute(required)]
+        tls: bool,
+    }
+
+    let attrs = [parse_quote!(#[test(user = "a", password = "b", tls = false)])];
+
+    assert_eq!(
+        Test::from_attributes(&attrs).unwrap().unwrap().value,
+        Test {
+            user: Some("a".to_string()),
+            password: Some("b".to_string()),
+            tls: false
+        }
+    );
+
+    let attrs = [parse_quote!(#[test(user = "a", tls)])];
+
+    let err = Test::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(err.to_string(), "`user` requires `password`");
+
+    let attrs = [parse_quote!(#[test(password = "b")])];
+
+    let err = Test::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.to_string(),
+        "missing `tls` flag, try `tls` or `tls = ...`"
+    );
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [common])]
+    struct Common {
+        #[attribute(default)]
+        doc: bool,
+    }
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [outer])]
+    struct Outer {
+        #[attribute(default, requires = [doc])]
+        hidden: bool,
+        #[attribute(flatten)]
+        common: Common,
+    }
+
+    let attrs = [parse_quote!(#[outer(hidden)])];
+
+    let err = Outer::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(err.to_string(), "`hidden` requires `doc`");
+}
+
+#[test]
+fn groups() {
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [source], one_of = [path, inline, url], any_of = [name, title])]
+    struct Source {
+        path: Option<String>,
+        inline: Option<String>,
+        url: Option<String>,
+        name: Option<String>,
+        title: Option<String>,
+    }
+
+    let attrs = [parse_quote!(#[source(path = "a.txt", name = "a")])];
+
+    assert_eq!(
+        Source::from_attributes(&attrs).unwrap().unwrap().value,
+        Source {
+            path: Some("a.txt".to_string()),
+            inline: None,
+            url: None,
+            name: Some("a".to_string()),
+            title: None
+        }
+    );
+
+    let attrs = [
+        parse_quote!(#[source(name = "a")]),
+        parse_quote!(#[source(title = "b")]),
+    ];
+
+    let err = Source::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.into_iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+        [
+            "missing one of: `path`, `inline`, `url`",
+            "missing one of: `path`, `inline`, `url`"
+        ]
+    );
+
+    let attrs = [parse_quote!(#[source(path = "a.txt", url = "b", inline = "c")])];
+
+    let err = Source::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.into_iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+        [
+            "expected only one of: `path`, `inline`, `url`",
+            "expected only one of: `path`, `inline`, `url`",
+            "expected only one of: `path`, `inline`, `url`",
+            "missing at least one of: `name`, `title`"
+        ]
+    );
+
+    let attrs = [parse_quote!(#[source(path = "a.txt")])];
+
+    let err = Source::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(err.to_string(), "missing at least one of: `name`, `title`");
+}
+
+#[test]
+fn conditions() {
+    #[derive(FromIdent, PartialEq, Eq, Debug, Default)]
+    enum Mode {
+        #[default]
+        Bounded,
+        Unbounded,
+    }
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [channel])]
+    struct Channel {
+        #[attribute(default)]
+        mode: Mode,
+        #[attribute(required_if = (mode = Mode::Bounded), forbidden_if = (mode = Mode::Unbounded))]
+        capacity: Option<usize>,
+        #[attribute(default, forbidden_if = (name = "main"))]
+        alias: Option<String>,
+        #[attribute(default)]
+        name: String,
+    }
+
+    let attrs = [parse_quote!(#[channel(mode = Bounded, capacity = 8)])];
+
+    assert_eq!(
+        Channel::from_attributes(&attrs).unwrap().unwrap().value,
+        Channel {
+            mode: Mode::Bounded,
+            capacity: Some(8),
+            alias: None,
+            name: String::new()
+        }
+    );
+
+    let attrs = [parse_quote!(#[channel(mode = Bounded)])];
+
+    let err = Channel::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.to_string(),
+        "missing `capacity` field, required when `mode = Mode::Bounded`"
+    );
+
+    // the default value of `mode` also triggers the condition
+    let attrs = [parse_quote!(#[channel()])];
+
+    assert!(Channel::from_attributes(&attrs).is_err());
+
+    let attrs = [parse_quote!(#[channel(mode = Unbounded, capacity = 8)])];
+
+    let err = Channel::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.to_string(),
+        "`capacity` cannot be given when `mode = Mode::Unbounded`"
+    );
+
+    let attrs = [parse_quote!(#[channel(capacity = 8, name = "main", alias = "a")])];
+
+    let err = Channel::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.to_string(),
+        "`alias` cannot be given when `name = \"main\"`"
+    );
+
+    // the errors of every condition are collected
+    let attrs =
+        [parse_quote!(#[channel(mode = Unbounded, capacity = 8, name = "main", alias = "a")])];
+
+    assert_eq!(
+        Channel::from_attributes(&attrs)
+            .unwrap_err()
+            .value
+            .into_iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>(),
+        [
+            "`capacity` cannot be given when `mode = Mode::Unbounded`",
+            "`alias` cannot be given when `name = \"main\"`"
+        ]
+    );
+}
+
+#[test]
+fn aliases() {
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [common])]
+    struct Common {
+        #[attribute(default, alias = "hide")]
+        hidden: bool,
+    }
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [test])]
+    struct Test {
+        #[attribute(positional, default)]
+        name: Option<String>,
+        #[attribute(rename = "type", alias = ["ty", "type_"], alias = "kind")]
+        ty: String,
+        #[attribute(flatten)]
+        common: Common,
+    }
+
+    for attrs in [
+        [parse_quote!(#[test(type = "u8")])],
+        [parse_quote!(#[test(ty = "u8")])],
+        [parse_quote!(#[test(type_ = "u8")])],
+        [parse_quote!(#[test(kind = "u8")])],
+    ] {
+        assert_eq!(
+            Test::from_attributes(&attrs).unwrap().unwrap().value,
+            Test {
+                name: None,
+                ty: "u8".to_string(),
+                common: Common { hidden: false }
+            }
+        );
+    }
+
+    let attrs = [parse_quote!(#[test(hide, ty = "u8")])];
+
+    assert_eq!(
+        Test::from_attributes(&attrs).unwrap().unwrap().value,
+        Test {
+            name: None,
+            ty: "u8".to_string(),
+            common: Common { hidden: true }
+        }
+    );
+
+    let attrs = [parse_quote!(#[test(ty = "u8", type_ = "u16")])];
+
+    let err = Test::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(err.to_string(), "duplicate `type` argument");
+
+    let attrs = [parse_quote!(#[test(tp = "u8")])];
+
+    let err = Test::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(err.to_string(), "expected fields: `name`, `type`, `hidden`");
+}
+
+#[test]
+fn rename_all() {
+    #[derive(FromIdent, PartialEq, Eq, Debug)]
+    #[attribute(rename_all = "SCREAMING_SNAKE_CASE")]
+    enum Mode {
+        ReadOnly,
+        ReadWrite,
+    }
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [test], rename_all = "camelCase")]
+    struct Test {
+        max_len: usize,
+        #[attribute(rename = "access")]
+        access_mode: Mode,
+        #[attribute(default)]
+        r#type: Option<String>,
+    }
+
+    let attrs = [parse_quote!(#[test(maxLen = 8, access = READ_WRITE, type = "a")])];
+
+    assert_eq!(
+        Test::from_attributes(&attrs).unwrap().unwrap().value,
+        Test {
+            max_len: 8,
+            access_mode: Mode::ReadWrite,
+            r#type: Some("a".to_string()),
+        }
+    );
+
+    let attrs = [parse_quote!(#[test(max_len = 8, access = READ_ONLY)])];
+
+    let err = Test::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.to_string(),
+        "unknown argument `max_len`, did you mean `maxLen`?"
+    );
+
+    let attrs = [parse_quote!(#[test(maxLen = 8, access = ReadOnly)])];
+
+    let err = Test::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.to_string(),
+        "invalid ident: `ReadOnly`, did you mean `READ_ONLY`?"
+    );
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [kind], rename_all = "PascalCase")]
+    enum Kind {
+        HttpServer,
+        Client { retry_count: usize },
+    }
+
+    let attrs = [parse_quote!(#[kind(Client(retry_count = 3))])];
+
+    assert_eq!(
+        Kind::from_attributes(&attrs).unwrap().unwrap().value,
+        Kind::Client { retry_count: 3 }
+    );
+
+    let attrs = [parse_quote!(#[kind(HttpServer)])];
+
+    assert_eq!(
+        Kind::from_attributes(&attrs).unwrap().unwrap().value,
+        Kind::HttpServer
+    );
+}
+
+#[test]
+fn suggestions() {
+    #[derive(FromIdent, PartialEq, Eq, Debug)]
+    enum Color {
+        Red,
+        Green,
+    }
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [inner], nested)]
+    struct Inner {
+        a: usize,
+    }
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [test])]
+    struct Test {
+        #[attribute(default)]
+        color: Option<Color>,
+        #[attribute(default)]
+        inner: Option<Inner>,
+        #[attribute(default)]
+        f0: bool,
+        #[attribute(default)]
+        f1: bool,
+        #[attribute(default)]
+        f2: bool,
+        #[attribute(default)]
+        f3: bool,
+        #[attribute(default)]
+        f4: bool,
+        #[attribute(default)]
+        f5: bool,
+        #[attribute(default)]
+        f6: bool,
+    }
+
+    let attrs = [parse_quote!(#[test(colour = Red)])];
+
+    let err = Test::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.to_string(),
+        "unknown argument `colour`, did you mean `color`?"
+    );
+
+    let attrs = [parse_quote!(#[test(colors = Red)])];
+
+    let err = Test::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.to_string(),
+        "unknown argument `colors`, did you mean `color`?"
+    );
+
+    let attrs = [parse_quote!(#[test(shade = Red)])];
+
+    let err = Test::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.to_string(),
+        "expected fields: `color`, `inner`, `f0`, `f1`, `f2`, `f3`, `f4`, `f5` and 1 other"
+    );
+
+    let attrs = [parse_quote!(#[test(color = Gren)])];
+
+    let err = Test::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.to_string(),
+        "invalid ident: `Gren`, did you mean `Green`?"
+    );
+
+    let attrs = [parse_quote!(#[test(color = Blue)])];
+
+    let err = Test::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.to_string(),
+        "invalid ident: `Blue`, valid idents: `Red`, `Green`"
+    );
+
+    let attrs = [parse_quote!(#[test(inner = iner(a = 1))])];
+
+    let err = Test::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.to_string(),
+        "unknown ident `iner`, did you mean `inner`?"
+    );
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [route])]
+    enum Route {
+        Get,
+        Post,
+    }
+
+    let attrs = [parse_quote!(#[route(gte)])];
+
+    let err = Route::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.to_string(),
+        "unknown argument `gte`, did you mean `get`?"
+    );
+}
+
+#[test]
+fn rest() {
+    use from_attr::Map;
+    use proc_macro2::TokenStream;
+    use syn::{Meta, Path};
+
+    #[derive(FromAttr)]
+    #[attribute(idents = [test])]
+    struct Test {
+        a: usize,
+        #[attribute(rest)]
+        rest: Vec<Meta>,
+    }
+
+    let attrs = [parse_quote!(#[test(b = 1 + 2, a = 1, c(d, e), serde::f)])];
+
+    let test = Test::from_attributes(&attrs).unwrap().unwrap().value;
+    assert_eq!(test.a, 1);
+    assert_eq!(
+        test.rest
+            .iter()
+            .map(|meta| quote!(#meta).to_string())
+            .collect::<Vec<_>>(),
+        ["b = 1 + 2", "c (d , e)", "serde :: f"]
+    );
+
+    #[derive(FromAttr)]
+    #[attribute(idents = [test])]
+    struct TestMap {
+        a: usize,
+        #[attribute(rest)]
+        rest: Map<Path, TokenStream>,
+    }
+
+    let attrs = [parse_quote!(#[test(a = 1, b = |x| x, c(d))])];
+
+    let test = TestMap::from_attributes(&attrs).unwrap().unwrap().value;
+    assert_eq!(test.a, 1);
+    assert_eq!(
+        test.rest
+            .0
+            .iter()
+            .map(|(path, tokens)| (quote!(#path).to_string(), tokens.to_string()))
+            .collect::<Vec<_>>(),
+        [
+            ("b".to_string(), "| x | x".to_string()),
+            ("c".to_string(), "d".to_string())
+        ]
+    );
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [test], allow_unknown)]
+    struct Lenient {
+        a: usize,
+    }
+
+    let attrs = [parse_quote!(#[test(b = 1, a = 1, c(d), e)])];
+
+    assert_eq!(
+        Lenient::from_attributes(&attrs).unwrap().unwrap().value,
+        Lenient { a: 1 }
+    );
+
+    let attrs = [parse_quote!(#[test(a = 1, a = 2)])];
+
+    let err = Lenient::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(err.to_string(), "duplicate `a` argument");
+}
+
+#[test]
+fn multiple() {
+    use std::collections::BTreeSet;
+
+    use from_attr::Map;
+    use syn::Path;
+
+    #[derive(FromAttr)]
+    #[attribute(idents = [derive_extra])]
+    struct Test {
+        #[attribute(rename = "trait", multiple)]
+        traits: Vec<Path>,
+        #[attribute(multiple)]
+        features: BTreeSet<String>,
+        #[attribute(multiple)]
+        env: Map<String, String>,
+    }
+
+    let attrs = [
+        parse_quote!(#[derive_extra(trait = Clone, features = "b", trait = [Debug, Hash])]),
+        parse_quote!(#[derive_extra(env = "A": "1", features = ["a", "b"], env = { "B": "2" })]),
+    ];
+
+    let test = Test::from_attributes(&attrs).unwrap().unwrap().value;
+    assert_eq!(
+        test.traits
+            .iter()
+            .map(|path| quote!(#path).to_string())
+            .collect::<Vec<_>>(),
+        ["Clone", "Debug", "Hash"]
+    );
+    assert_eq!(
+        test.features.into_iter().collect::<Vec<_>>(),
+        ["a".to_string(), "b".to_string()]
+    );
+    assert_eq!(
+        test.env.0,
+        [
+            ("A".to_string(), "1".to_string()),
+            ("B".to_string(), "2".to_string())
+        ]
+    );
+
+    let attrs = [parse_quote!(#[derive_extra()])];
+
+    let test = Test::from_attributes(&attrs).unwrap().unwrap().value;
+    assert!(test.traits.is_empty());
+    assert!(test.features.is_empty());
+    assert!(test.env.0.is_empty());
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [test])]
+    struct Unique {
+        #[attribute(multiple, unique, max_len = 3)]
+        a: Vec<usize>,
+    }
+
+    let attrs = [parse_quote!(#[test(a = 1, a = [2, 3])])];
+
+    assert_eq!(
+        Unique::from_attributes(&attrs).unwrap().unwrap().value,
+        Unique { a: vec![1, 2, 3] }
+    );
+
+    let attrs = [parse_quote!(#[test(a = 1, a = [2, 1])])];
+
+    let err = Unique::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(err.to_string(), "`a` must not contain duplicate elements");
+
+    let attrs = [parse_quote!(#[test(a = [1, 2], a = [3, 4])])];
+
+    let err = Unique::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(err.to_string(), "`a` must have a length of at most 3");
+}
+
+#[test]
+fn merge() {
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [common])]
+    struct Common {
+        #[attribute(default)]
+        doc: Option<String>,
+    }
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [test], merge = "last")]
+    struct Test {
+        #[attribute(positional, default)]
+        name: Option<String>,
+        #[attribute(default)]
+        a: usize,
+        #[attribute(default, merge = "first")]
+        b: usize,
+        #[attribute(default, merge = "append")]
+        c: Vec<usize>,
+        #[attribute(default, merge = "error")]
+        d: usize,
+        #[attribute(flatten)]
+        common: Common,
+    }
+
+    let attrs = [
+        parse_quote!(#[test("x", a = 1, b = 1, c = [1], d = 1)]),
+        parse_quote!(#[test("y", a = 2, b = 2, c = [2, 3])]),
+    ];
+
+    assert_eq!(
+        Test::from_attributes(&attrs).unwrap().unwrap().value,
+        Test {
+            name: Some("y".to_string()),
+            a: 2,
+            b: 1,
+            c: vec![1, 2, 3],
+            d: 1,
+            common: Common { doc: None }
+        }
+    );
+
+    let attrs = [parse_quote!(#[test(a = 1, a = 2)])];
+
+    let err = Test::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(
+        err.into_iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+        ["duplicate `a` argument", "note: first defined here"]
+    );
+
+    let attrs = [parse_quote!(#[test(d = 1)]), parse_quote!(#[test(d = 2)])];
+
+    let err = Test::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(err.to_string(), "duplicate `d` argument");
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [common], merge = "last")]
+    struct LastCommon {
+        #[attribute(default)]
+        doc: Option<String>,
+    }
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [outer])]
+    struct Outer {
+        #[attribute(default)]
+        a: usize,
+        #[attribute(flatten)]
+        common: LastCommon,
+    }
+
+    let attrs = [
+        parse_quote!(#[outer(doc = "a")]),
+        parse_quote!(#[outer(doc = "b")]),
+    ];
+
+    assert_eq!(
+        Outer::from_attributes(&attrs).unwrap().unwrap().value,
+        Outer {
+            a: 0,
+            common: LastCommon {
+                doc: Some("b".to_string())
+            }
+        }
+    );
+
+    let attrs = [parse_quote!(#[outer(a = 1)]), parse_quote!(#[outer(a = 2)])];
+
+    // the merge strategy of the flattened field does not apply to the outer fields
+    let err = Outer::from_attributes(&attrs).unwrap_err().value;
+    assert_eq!(err.to_string(), "duplicate `a` argument");
+}
+
+#[test]
+fn attributes_each() {
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [route])]
+    struct Route {
+        #[attribute(positional)]
+        path: String,
+        #[attribute(default)]
+        method: Option<String>,
+    }
+
+    let attrs = [
+        parse_quote!(#[route("/a")]),
+        parse_quote!(#[doc = "x"]),
+        parse_quote!(#[route("/b", method = "POST")]),
+    ];
+
+    let values = Route::from_attributes_each(&attrs).unwrap();
+    assert_eq!(values.len(), 2);
+    assert_eq!(values[0].attrs, [&attrs[0]]);
+    assert_eq!(
+        values
+            .into_iter()
+            .map(|attrs_value| attrs_value.value)
+            .collect::<Vec<_>>(),
+        [
+            Route {
+                path: "/a".to_string(),
+                method: None
+            },
+            Route {
+                path: "/b".to_string(),
+                method: Some("POST".to_string())
+            }
+        ]
+    );
+
+    let attrs = [
+        parse_quote!(#[route(method = "GET")]),
+        parse_quote!(#[route("/b", path = "/c")]),
+    ];
+
+    let err = Route::from_attributes_each(&attrs).unwrap_err();
+    assert_eq!(err.attrs.len(), 2);
+    assert_eq!(
+        err.value
+            .into_iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>(),
+        [
+            "missing `path` field, try `path = ...`",
+            "duplicate `path` argument",
+            "note: first defined here"
+        ]
+    );
+
+    let mut attrs = vec![
+        parse_quote!(#[route("/a")]),
+        parse_quote!(#[doc = "x"]),
+        parse_quote!(#[route("/b")]),
+    ];
+
+    let values = Route::remove_attributes_each(&mut attrs).unwrap();
+    assert_eq!(attrs.len(), 1);
+    assert_eq!(values.len(), 2);
+    assert_eq!(values[1].value.path, "/b");
+}
+
+#[test]
+fn once() {
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [config], once)]
+    struct Config {
+        #[attribute(default)]
+        a: usize,
+        #[attribute(default)]
+        b: usize,
+    }
+
+    let attrs = [parse_quote!(#[config(a = 1, b = 2)])];
+
+    assert_eq!(
+        Config::from_attributes(&attrs).unwrap().unwrap().value,
+        Config { a: 1, b: 2 }
+    );
+
+    let attrs = [
+        parse_quote!(#[config(a = 1)]),
+        parse_quote!(#[config(b = 2)]),
+        parse_quote!(#[config(b = 3)]),
+    ];
+
+    assert_eq!(
+        Config::from_attributes(&attrs)
+            .unwrap_err()
+            .value
+            .into_iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>(),
+        [
+            "duplicate `config` attribute\nhelp: merge the arguments into one `config` attribute",
+            "note: the first `config` attribute is here",
+            "duplicate `config` attribute\nhelp: merge the arguments into one `config` attribute",
+            "note: the first `config` attribute is here"
+        ]
+    );
+
+    let mut attrs = vec![
+        parse_quote!(#[config(a = 1)]),
+        parse_quote!(#[config(b = 2)]),
+        parse_quote!(#[config(b = 3)]),
+    ];
+    let err = Config::remove_attributes(&mut attrs).unwrap_err();
+    assert!(attrs.is_empty());
+    assert_eq!(err.attrs.len(), 3);
+    assert_eq!(err.value.into_iter().count(), 4);
+}
+
+#[test]
+fn skip_and_default_with() {
+    fn ident_of(name: &str) -> syn::Ident {
+        syn::Ident::new(&name.to_lowercase(), proc_macro2::Span::call_site())
+    }
+
+    fn double(count: &usize) -> usize {
+        count * 2
+    }
+
+    fn empty() -> Vec<String> {
+        Vec::new()
+    }
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [test])]
+    struct Test {
+        name: String,
+        #[attribute(default)]
+        count: usize,
+        #[attribute(skip, default_with = (ident_of, [name]))]
+        ident: syn::Ident,
+        #[attribute(default_with = (double, [count]))]
+        limit: usize,
+        #[attribute(skip)]
+        cache: Vec<String>,
+        #[attribute(default_with = empty)]
+        tags: Vec<String>,
+    }
+
+    let attrs = [parse_quote!(#[test(name = "Foo", count = 2)])];
+
+    assert_eq!(
+        Test::from_attributes(&attrs).unwrap().unwrap().value,
+        Test {
+            name: "Foo".to_string(),
+            count: 2,
+            ident: syn::Ident::new("foo", proc_macro2::Span::call_site()),
+            limit: 4,
+            cache: Vec::new(),
+            tags: Vec::new(),
+        }
+    );
+
+    let attrs = [parse_quote!(#[test(name = "Foo", limit = 1)])];
+
+    assert_eq!(
+        Test::from_attributes(&attrs).unwrap().unwrap().value.limit,
+        1
+    );
+
+    let attrs = [parse_quote!(#[test(name = "Foo", ident = foo)])];
+
+    assert_eq!(
+        Test::from_attributes(&attrs).unwrap_err().value.to_string(),
+        "expected fields: `name`, `count`, `limit`, `tags`"
+    );
+}
+
+#[test]
+fn struct_default() {
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [test], default)]
+    struct Test {
+        a: usize,
+        #[attribute(default = 2)]
+        b: usize,
+        #[attribute(required)]
+        c: Option<String>,
+    }
+
+    let attrs = [parse_quote!(#[test(c = "c")])];
+
+    assert_eq!(
+        Test::from_attributes(&attrs).unwrap().unwrap().value,
+        Test {
+            a: 0,
+            b: 2,
+            c: Some("c".to_string()),
+        }
+    );
+
+    let attrs = [parse_quote!(#[test(a = 1)])];
+
+    assert_eq!(
+        Test::from_attributes(&attrs).unwrap_err().value.to_string(),
+        "missing `c` field, try `c = ...`"
+    );
+
+    let attrs: [syn::Attribute; 1] = [parse_quote!(#[doc = "x"])];
+
+    assert_eq!(
+        Test::from_attributes_or_default(&attrs)
+            .unwrap_err()
+            .value
+            .to_string(),
+        "missing `#[test(...)]` attribute"
+    );
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [test], default)]
+    struct Optional {
+        a: usize,
+        #[attribute(default = 2)]
+        b: usize,
+    }
+
+    // the absent attribute has the same defaults as an empty one
+    assert_eq!(
+        Optional::from_attributes_or_default(&attrs).unwrap(),
+        Optional { a: 0, b: 2 }
+    );
+
+    assert_eq!(
+        Optional::from_attributes(&[parse_quote!(#[test])])
+            .unwrap()
+            .unwrap()
+            .value,
+        Optional { a: 0, b: 2 }
+    );
+
+    let err = Test::require_from_attributes(&attrs, proc_macro2::Span::call_site()).unwrap_err();
+    assert!(err.attrs.is_empty());
+    assert_eq!(err.value.to_string(), "missing `#[test(...)]` attribute");
+
+    let attrs = [parse_quote!(#[test(a = 1, c = "c")])];
+
+    assert_eq!(
+        Test::require_from_attributes(&attrs, proc_macro2::Span::call_site())
+            .unwrap()
+            .value
+            .a,
+        1
+    );
+}
+
+#[test]
+fn negatable_and_flag() {
+    const DEFAULT_JOBS: usize = 4;
+
+    #[derive(FromIdent, PartialEq, Eq, Debug)]
+    enum Mode {
+        Fast,
+        Slow,
+    }
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [test])]
+    struct Test {
+        #[attribute(negatable, default = true)]
+        color: bool,
+        #[attribute(flag = 1, default)]
+        verbose: usize,
+        #[attribute(flag = String::from("main"))]
+        entry: String,
+    }
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [test])]
+    struct Build {
+        #[attribute(flag = DEFAULT_JOBS, default = 1)]
+        jobs: usize,
+        #[attribute(flag = Mode::Fast, default = Mode::Slow)]
+        mode: Mode,
+    }
+
+    let attrs = [parse_quote!(#[test(jobs, mode)])];
+
+    assert_eq!(
+        Build::from_attributes(&attrs).unwrap().unwrap().value,
+        Build {
+            jobs: 4,
+            mode: Mode::Fast,
+        }
+    );
+
+    let attrs = [parse_quote!(#[test(jobs = 2, mode = Slow)])];
+
+    assert_eq!(
+        Build::from_attributes(&attrs).unwrap().unwrap().value,
+        Build {
+            jobs: 2,
+            mode: Mode::Slow,
+        }
+    );
+
+    let attrs = [parse_quote!(#[test(entry)])];
+
+    assert_eq!(
+        Test::from_attributes(&attrs).unwrap().unwrap().value,
+        Test {
+            color: true,
+            verbose: 0,
+            entry: "main".to_string(),
+        }
+    );
+
+    let attrs = [parse_quote!(#[test(no_color, verbose, entry = "start")])];
+
+    assert_eq!(
+        Test::from_attributes(&attrs).unwrap().unwrap().value,
+        Test {
+            color: false,
+            verbose: 1,
+            entry: "start".to_string(),
+        }
+    );
+
+    let attrs = [parse_quote!(#[test(color = false, verbose = 3, entry)])];
+
+    assert_eq!(
+        Test::from_attributes(&attrs).unwrap().unwrap().value,
+        Test {
+            color: false,
+            verbose: 3,
+            entry: "main".to_string(),
+        }
+    );
+
+    let attrs = [parse_quote!(#[test(no_color, color, entry)])];
+
+    assert_eq!(
+        Test::from_attributes(&attrs)
+            .unwrap_err()
+            .value
+            .into_iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>(),
+        [
+            "`color` conflicts with mutually exclusive `no_color`",
+            "note: `no_color` is given here"
+        ]
+    );
+
+    let attrs = [parse_quote!(#[test(color, no_color, entry)])];
+
+    assert_eq!(
+        Test::from_attributes(&attrs)
+            .unwrap_err()
+            .value
+            .into_iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>(),
+        [
+            "`no_color` conflicts with mutually exclusive `color`",
+            "note: `color` is given here"
+        ]
+    );
+
+    let attrs = [parse_quote!(#[test(no_color = true, entry)])];
+
+    assert_eq!(
+        Test::from_attributes(&attrs).unwrap_err().value.to_string(),
+        "`no_color` does not take a value"
+    );
+
+    let attrs = [parse_quote!(#[test(no_color)])];
+
+    assert_eq!(
+        Test::from_attributes(&attrs).unwrap_err().value.to_string(),
+        "missing `entry` flag, try `entry` or `entry = ...`"
+    );
+
+    // with `merge`, `color` and `no_color` are merged instead of conflicting
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [test])]
+    struct Merged {
+        #[attribute(negatable, default, merge = "last")]
+        last: bool,
+        #[attribute(negatable, default, merge = "first")]
+        first: bool,
+    }
+
+    let attrs = [parse_quote!(#[test(last, first, no_last, no_first)])];
+
+    assert_eq!(
+        Merged::from_attributes(&attrs).unwrap().unwrap().value,
+        Merged {
+            last: false,
+            first: true,
+        }
+    );
+
+    let attrs = [
+        parse_quote!(#[test(no_last, no_first)]),
+        parse_quote!(#[test(last, first)]),
+    ];
+
+    assert_eq!(
+        Merged::from_attributes(&attrs).unwrap().unwrap().value,
+        Merged {
+            last: true,
+            first: false,
+        }
+    );
+}
+
+#[test]
+fn spanned() {
+    #[derive(FromAttr)]
+    #[attribute(idents = [test])]
+    struct Test {
+        route: Spanned<String>,
+        #[attribute(default)]
+        limits: Vec<Spanned<usize>>,
+        #[attribute(default)]
+        weights: Map<String, Spanned<usize>>,
+        name: Option<Spanned<String>>,
+        #[attribute(default)]
+        flag: Spanned<bool>,
+    }
+
+    let attrs = [parse_quote!(#[test(
+        route = "/a",
+        limits = [1, 2],
+        weights = { "a": 3 },
+        flag
+    )])];
+
+    let test = Test::from_attributes(&attrs).unwrap().unwrap().value;
+
+    assert_eq!(test.route.value, "/a");
+    assert_eq!(
+        test.limits.iter().map(|a| a.value).collect::<Vec<_>>(),
+        [1, 2]
+    );
+    assert_eq!(test.weights.0[0].0, "a");
+    assert_eq!(test.weights.0[0].1.value, 3);
+    assert!(test.name.is_none());
+    assert!(test.flag.value);
+
+    let attrs = [parse_quote!(#[test(route = "/b", name = "b")])];
+
+    let test = Test::from_attributes(&attrs).unwrap().unwrap().value;
+
+    assert_eq!(test.name.unwrap().value, "b");
+    assert!(test.limits.is_empty());
+    assert!(!test.flag.value);
+}
+
+#[test]
+fn error_accumulation() {
+    #[allow(dead_code)]
+    #[derive(FromAttr, Debug)]
+    #[attribute(idents = [test])]
+    struct Test {
+        #[attribute(conflicts = [b])]
+        a: u8,
+        #[attribute(default)]
+        b: usize,
+        c: String,
+        d: String,
+    }
+
+    let attrs =
+        [parse_quote!(#[test(a = "x", unknown = 1, b = 1, b = 2, c = "c", c = 3, d = "d")])];
+
+    assert_eq!(
+        Test::from_attributes(&attrs)
+            .unwrap_err()
+            .value
+            .into_iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>(),
+        [
+            "expected integer literal",
+            "expected fields: `a`, `b`, `c`, `d`",
+            "duplicate `b` argument",
+            "note: first defined here",
+            "duplicate `c` argument",
+            "note: first defined here"
+        ]
+    );
+
+    let attrs = [parse_quote!(#[test(a = 300, b = 1)])];
+
+    assert_eq!(
+        Test::from_attributes(&attrs)
+            .unwrap_err()
+            .value
+            .into_iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>(),
+        [
+            "`a` conflicts with mutually exclusive `b`",
+            "note: `b` is given here",
+            "number too large to fit in target type",
+            "missing `c` field, try `c = ...`",
+            "missing `d` field, try `d = ...`"
+        ]
+    );
+}
+
+#[test]
+fn diagnostics() {
+    use from_attr::{Diagnostic, Diagnostics, ErrorKind, PathValue};
+    use proc_macro2::Span;
+
+    fn not_zero(value: PathValue<&usize>) -> syn::Result<()> {
+        if *value.value == 0 {
+            Err(Diagnostic::new(value.path, "must not be zero")
+                .help("use at least `1`")
+                .into())
+        } else {
+            Ok(())
+        }
+    }
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [test])]
+    struct Test {
+        #[attribute(validate = not_zero)]
+        a: usize,
+        #[attribute(default)]
+        b: usize,
+    }
+
+    let attrs = [
+        parse_quote!(#[test(a = 0, b = 1)]),
+        parse_quote!(#[test(b = 2)]),
+    ];
+
+    assert_eq!(
+        Test::from_attributes(&attrs)
+            .unwrap_err()
+            .value
+            .into_iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>(),
+        ["duplicate `b` argument", "note: first defined here"]
+    );
+
+    let attrs = [parse_quote!(#[test(a = 0)])];
+
+    assert_eq!(
+        Test::from_attributes(&attrs).unwrap_err().value.to_string(),
+        "must not be zero\nhelp: use at least `1`"
+    );
+
+    let diagnostic = Diagnostic::new(Span::call_site(), "primary").note(Span::call_site(), "here");
+    let tokens = diagnostic.to_compile_error().to_string();
+    assert_eq!(tokens.matches("compile_error").count(), 2);
+    assert!(tokens.contains("\"note: here\""));
+
+    let mut errors = Diagnostics::new();
+    assert!(errors.is_empty());
+
+    errors.push(diagnostic);
+    errors.extend(
+        Diagnostics::spanned(
+            &[Span::call_site(), Span::call_site()],
+            ErrorKind::MissingAttribute,
+            "missing",
+        )
+        .into_error(),
+    );
+
+    assert_eq!(
+        errors
+            .finish(())
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>(),
+        ["primary", "note: here", "missing", "missing"]
+    );
+}
+
+#[test]
+fn error_kinds() {
+    use from_attr::{ErrorKind, PathValue};
+
+    fn not_zero(value: PathValue<&usize>) -> syn::Result<()> {
+        if *value.value == 0 {
+            Err(syn::Error::new(value.path, "must not be zero"))
+        } else {
+            Ok(())
+        }
+    }
+
+    #[derive(FromAttr, PartialEq, Eq, Debug)]
+    #[attribute(idents = [test])]
+    struct Test {
+        #[attribute(default, validate = not_zero)]
+        a: usize,
+        #[attribute(default, conflicts = [c])]
+        b: bool,
+        #[attribute(default)]
+        c: bool,
+        #[attribute(default)]
+        d: Option<u8>,
+    }
+
+    let attrs = [parse_quote!(#[test(a = 1, a = 2)])];
+    let err = Test::from_attributes(&attrs).unwrap_err().value;
+    assert!(matches!(err.kind(), ErrorKind::DuplicateField { name, .. } if name == "a"));
+
+    let attrs = [parse_quote!(#[test(b, c)])];
+    let err = Test::from_attributes(&attrs).unwrap_err().value;
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::Conflict { name, other, .. } if name == "b" && other == "c"
+    ));
+
+    let attrs = [parse_quote!(#[test(a = 0, d = 300)])];
+    let err = Test::from_attributes(&attrs).unwrap_err().value;
+    let kinds = err.kinds().collect::<Vec<_>>();
+    assert!(matches!(kinds[0], ErrorKind::Validation { name: Some(name) } if name == "a"));
+    assert!(matches!(kinds[1], ErrorKind::Conversion { name: Some(name) } if name == "d"));
+
+    let err = syn::Error::from(err);
+    assert_eq!(err.to_string(), "must not be zero");
+
+    // a literal of the wrong type cannot be converted
+    let attrs = [parse_quote!(#[test(d = "x")])];
+    let err = Test::from_attributes(&attrs).unwrap_err().value;
+    assert!(matches!(err.kind(), ErrorKind::Conversion { name: Some(name) } if name == "d"));
+    assert_eq!(err.to_string(), "expected integer literal");
+}